@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+use sdl2::image::ImageRWops;
+use sdl2::rwops::RWops;
+use crate::engine::engine_error::EngineError;
+use crate::engine::rendering::texture::{create_texture_from_surface, Texture};
+
+pub struct TextureUsage {
+    pub path: String,
+    pub reference_count: usize,
+    pub byte_size: usize,
+}
+
+// 以路徑快取貼圖，只保留弱參照，沒有人使用時貼圖就會被釋放
+pub struct AssetManager {
+    textures: HashMap<String, Weak<Texture>>,
+}
+
+impl AssetManager {
+    pub fn new() -> Self {
+        Self {
+            textures: HashMap::new(),
+        }
+    }
+
+    pub fn load_texture(&mut self, path: &str) -> Result<Rc<Texture>, EngineError> {
+        if let Some(texture) = self.textures.get(path).and_then(Weak::upgrade) {
+            return Ok(texture);
+        }
+
+        let texture = Rc::new(load_texture_from_image(path)?);
+        self.textures.retain(|_, texture| texture.strong_count() > 0);
+        self.textures.insert(path.to_string(), Rc::downgrade(&texture));
+        Ok(texture)
+    }
+
+    pub fn get_texture_usages(&self) -> Vec<TextureUsage> {
+        let mut texture_usages = self.textures
+            .iter()
+            .filter_map(|(path, texture)| {
+                let texture = texture.upgrade()?;
+                Some(TextureUsage {
+                    path: path.clone(),
+                    // 扣掉這裡暫時取得的參照
+                    reference_count: Rc::strong_count(&texture) - 1,
+                    byte_size: texture.get_byte_size(),
+                })
+            })
+            .collect::<Vec<_>>();
+        texture_usages.sort_by(|a, b| a.path.cmp(&b.path));
+        texture_usages
+    }
+
+    pub fn get_texture_memory_usage(&self) -> usize {
+        self.get_texture_usages()
+            .iter()
+            .map(|texture_usage| texture_usage.byte_size)
+            .sum()
+    }
+
+    pub fn log_texture_usages(&self) {
+        let texture_usages = self.get_texture_usages();
+        for texture_usage in &texture_usages {
+            eprintln!("{} (refs: {}, {} KB)",
+                      texture_usage.path,
+                      texture_usage.reference_count,
+                      texture_usage.byte_size / 1024);
+        }
+        eprintln!("{} textures loaded, {} KB in total",
+                  texture_usages.len(),
+                  self.get_texture_memory_usage() / 1024);
+    }
+}
+
+fn load_texture_from_image(image_path: &str) -> Result<Texture, EngineError> {
+    let bytes = std::fs::read(image_path)
+        .map_err(|error| EngineError::Io { path: image_path.to_string(), error })?;
+    let surface = RWops::from_bytes(&bytes)
+        .and_then(|rwops| rwops.load())
+        .map_err(|message| EngineError::ImageDecode { path: image_path.to_string(), message })?;
+    create_texture_from_surface(surface)
+}
@@ -1,29 +1,49 @@
 use sdl2::mixer::{Chunk, InitFlag, Music, AUDIO_S16LSB, DEFAULT_CHANNELS};
+use crate::engine::engine_error::EngineError;
+
+const VOICE_CHANNEL: i32 = 0;
+const CHANNEL_COUNT: i32 = 4;
 
 pub struct AudioManager<'a> {
     current_music: Option<Music<'a>>,
     current_sound: Option<Chunk>,
+    current_voice: Option<Chunk>,
     pub is_started: bool,
+    // 音效裝置無法開啟時所有播放都會被略過
+    is_available: bool,
 }
 
 impl<'a> AudioManager<'a> {
     pub fn new(is_web: bool) -> Self {
-        sdl2::mixer::init(InitFlag::MP3).unwrap();
-        sdl2::mixer::open_audio(44100, AUDIO_S16LSB, DEFAULT_CHANNELS, 1024).unwrap();
-        sdl2::mixer::allocate_channels(4);
-        sdl2::mixer::Music::set_volume(sdl2::mixer::MAX_VOLUME / 2);
-        sdl2::mixer::Channel::all().set_volume(sdl2::mixer::MAX_VOLUME / 2);
+        let is_available = match open_audio() {
+            Ok(_) => true,
+            Err(error) => {
+                eprintln!("{}", error);
+                false
+            }
+        };
 
         Self {
             current_music: None,
             current_sound: None,
-            is_started: !is_web
+            current_voice: None,
+            is_started: !is_web,
+            is_available,
         }
     }
 
+    // 讀取失敗時記錄錯誤並停止目前的音樂
     pub fn load_music(&mut self, file_path: &str) {
-        let music = Music::from_file(file_path).unwrap();
-        self.current_music = Some(music);
+        if !self.is_available {
+            return;
+        }
+
+        self.current_music = Music::from_file(file_path)
+            .map_err(|message| eprintln!("{}", EngineError::Audio { path: file_path.to_string(), message }))
+            .ok();
+        if self.current_music.is_none() {
+            sdl2::mixer::Music::halt();
+        }
     }
 
     pub fn play_music(&self) {
@@ -32,14 +52,76 @@ impl<'a> AudioManager<'a> {
         }
 
         if let Some(music) = &self.current_music {
-            music.play(-1).unwrap();
+            if let Err(message) = music.play(-1) {
+                eprintln!("Can't play music: {}", message);
+            }
         }
     }
 
     pub fn play_sound_one_shot(&mut self, file_path: &str){
-        let chunk = Chunk::from_file(file_path).unwrap();
-        sdl2::mixer::Channel::all().play(&chunk, 0).unwrap();
-        self.current_sound = Some(chunk);
+        if !self.is_available {
+            return;
+        }
+
+        match play_chunk(file_path, sdl2::mixer::Channel::all()) {
+            Ok(chunk) => self.current_sound = Some(chunk),
+            Err(error) => eprintln!("{}", error),
+        }
+    }
+
+    // 回傳語音長度，播放失敗時為 0
+    pub fn play_voice(&mut self, file_path: &str) -> f32 {
+        if !self.is_available {
+            return 0.0;
+        }
+
+        match play_chunk(file_path, sdl2::mixer::Channel(VOICE_CHANNEL)) {
+            Ok(chunk) => {
+                let duration = get_chunk_duration(&chunk);
+                self.current_voice = Some(chunk);
+                duration
+            },
+            Err(error) => {
+                eprintln!("{}", error);
+                0.0
+            }
+        }
+    }
+
+    pub fn stop_voice(&mut self) {
+        if !self.is_available {
+            return;
+        }
+
+        sdl2::mixer::Channel(VOICE_CHANNEL).halt();
+        self.current_voice = None;
+    }
+
+    // 音量皆為 0.0 ~ 1.0
+    pub fn set_music_volume(&self, volume: f32) {
+        if !self.is_available {
+            return;
+        }
+
+        sdl2::mixer::Music::set_volume(to_mixer_volume(volume));
+    }
+
+    pub fn set_sound_volume(&self, volume: f32) {
+        if !self.is_available {
+            return;
+        }
+
+        for channel in VOICE_CHANNEL + 1..CHANNEL_COUNT {
+            sdl2::mixer::Channel(channel).set_volume(to_mixer_volume(volume));
+        }
+    }
+
+    pub fn set_voice_volume(&self, volume: f32) {
+        if !self.is_available {
+            return;
+        }
+
+        sdl2::mixer::Channel(VOICE_CHANNEL).set_volume(to_mixer_volume(volume));
     }
 
     pub fn start_music(&mut self){
@@ -51,3 +133,40 @@ impl<'a> AudioManager<'a> {
         self.play_music();
     }
 }
+
+fn open_audio() -> Result<(), EngineError> {
+    sdl2::mixer::init(InitFlag::MP3).map_err(EngineError::Sdl)?;
+    sdl2::mixer::open_audio(44100, AUDIO_S16LSB, DEFAULT_CHANNELS, 1024).map_err(EngineError::Sdl)?;
+    sdl2::mixer::allocate_channels(CHANNEL_COUNT);
+    // 第 0 聲道保留給語音，音效不會搶用
+    sdl2::mixer::reserve_channels(VOICE_CHANNEL + 1);
+    sdl2::mixer::Music::set_volume(sdl2::mixer::MAX_VOLUME / 2);
+    sdl2::mixer::Channel::all().set_volume(sdl2::mixer::MAX_VOLUME / 2);
+    Ok(())
+}
+
+fn play_chunk(file_path: &str, channel: sdl2::mixer::Channel) -> Result<Chunk, EngineError> {
+    let to_audio_error = |message: String| EngineError::Audio { path: file_path.to_string(), message };
+    let chunk = Chunk::from_file(file_path).map_err(to_audio_error)?;
+    channel.play(&chunk, 0).map_err(to_audio_error)?;
+    Ok(chunk)
+}
+
+fn to_mixer_volume(volume: f32) -> i32 {
+    (volume.clamp(0.0, 1.0) * sdl2::mixer::MAX_VOLUME as f32).round() as i32
+}
+
+fn get_chunk_duration(chunk: &Chunk) -> f32 {
+    let Ok((frequency, format, channels)) = sdl2::mixer::query_spec() else {
+        return 0.0;
+    };
+    let bytes_per_sample = (format & 0xFF) as u32 / 8;
+    let bytes_per_second = frequency as u32 * channels as u32 * bytes_per_sample;
+    if bytes_per_second == 0 || chunk.raw.is_null() {
+        return 0.0;
+    }
+    // SAFETY: chunk.raw 是 Chunk::from_file 成功時由 SDL_mixer 配置的 Mix_Chunk，上面已確認不是空指標，
+    // 並且在 chunk 被 drop（Mix_FreeChunk）之前都有效；這裡只讀取長度欄位，不會寫入
+    let length = unsafe { (*chunk.raw).alen };
+    length as f32 / bytes_per_second as f32
+}
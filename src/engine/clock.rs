@@ -0,0 +1,97 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use web_time::Instant;
+
+// 回傳從時鐘建立後經過的秒數
+pub trait Clock {
+    fn now(&self) -> f64;
+
+    // 每幀開始時呼叫，只有固定幀間隔的時鐘需要在這裡前進
+    fn advance_frame(&self) {}
+}
+
+pub struct RealClock {
+    start_time: Instant,
+}
+
+impl RealClock {
+    pub fn new() -> Self {
+        Self {
+            start_time: Instant::now(),
+        }
+    }
+}
+
+impl Clock for RealClock {
+    fn now(&self) -> f64 {
+        self.start_time.elapsed().as_secs_f64()
+    }
+}
+
+// 時間只在呼叫 advance 時前進，讀取不會改變時間，複製出來的時鐘共用同一個時間
+// frame_time 大於 0 時每幀自動前進固定秒數，用於重播與錄影
+#[derive(Clone)]
+pub struct ManualClock {
+    current_time: Rc<Cell<f64>>,
+    frame_time: f64,
+}
+
+impl ManualClock {
+    pub fn new(frame_time: f64) -> Self {
+        Self {
+            current_time: Rc::new(Cell::new(0.0)),
+            frame_time,
+        }
+    }
+
+    pub fn advance(&self, seconds: f64) {
+        self.current_time.set(self.current_time.get() + seconds);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> f64 {
+        self.current_time.get()
+    }
+
+    fn advance_frame(&self) {
+        if self.frame_time > 0.0 {
+            self.advance(self.frame_time);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::transition::Transition;
+
+    #[test]
+    fn manual_clock_only_moves_when_advanced() {
+        let clock = ManualClock::new(0.0);
+        clock.advance(0.25);
+        assert_eq!(clock.now(), 0.25);
+        assert_eq!(clock.now(), 0.25);
+
+        let shared_clock = clock.clone();
+        shared_clock.advance(0.5);
+        assert_eq!(clock.now(), 0.75);
+    }
+
+    #[test]
+    fn fixed_frame_time_steps_transition() {
+        let clock = ManualClock::new(0.25);
+        let transition = Transition::Fade(1.0);
+        let mut last_time = clock.now();
+        let mut elapsed_time = 0.0;
+        let mut cover_ratios = vec![];
+        for _ in 0..4 {
+            clock.advance_frame();
+            let current_time = clock.now();
+            elapsed_time += (current_time - last_time) as f32;
+            last_time = current_time;
+            cover_ratios.push(transition.get_cover_ratio(elapsed_time));
+        }
+        assert_eq!(cover_ratios, vec![0.5, 1.0, 0.5, 0.0]);
+    }
+}
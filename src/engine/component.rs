@@ -1,6 +1,9 @@
+use std::any::Any;
 use super::drawable_component::DrawableComponent;
 
 pub trait Component {
-    fn update(&mut self);
+    fn update(&mut self, delta_time: f32);
     fn as_drawable(&self) -> Option<&dyn DrawableComponent>;
-}
\ No newline at end of file
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
@@ -0,0 +1,28 @@
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DesignResolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl DesignResolution {
+    pub const LANDSCAPE_16_9: DesignResolution = DesignResolution { width: 1920, height: 1080 };
+    pub const STANDARD_4_3: DesignResolution = DesignResolution { width: 1440, height: 1080 };
+    pub const PORTRAIT_9_16: DesignResolution = DesignResolution { width: 1080, height: 1920 };
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "16:9" => Some(Self::LANDSCAPE_16_9),
+            "4:3" => Some(Self::STANDARD_4_3),
+            "9:16" => Some(Self::PORTRAIT_9_16),
+            _ => None,
+        }
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.width as f32, self.height as f32)
+    }
+
+    pub fn scaled(&self, scale: f32) -> (u32, u32) {
+        ((self.width as f32 * scale) as u32, (self.height as f32 * scale) as u32)
+    }
+}
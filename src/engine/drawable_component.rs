@@ -1,5 +1,10 @@
 use nalgebra_glm::Mat4;
+use crate::engine::rendering::sprite_batch::SpriteBatch;
 
 pub trait DrawableComponent {
-    fn draw(&self, projection_matrix: Mat4);
-}
\ No newline at end of file
+    fn draw_with_transform(&self, sprite_batch: &mut SpriteBatch, parent_matrix: &Mat4);
+
+    fn draw(&self, sprite_batch: &mut SpriteBatch) {
+        self.draw_with_transform(sprite_batch, &Mat4::identity());
+    }
+}
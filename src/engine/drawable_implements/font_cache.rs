@@ -0,0 +1,52 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+use sdl2::ttf::{Font, Sdl2TtfContext};
+use crate::engine::drawable_implements::glyph_atlas::GlyphAtlas;
+use crate::engine::engine_error::EngineError;
+
+// 字型檔很大，同樣的路徑與大小只讀取一次
+pub struct FontCache {
+    ttf_context: &'static Sdl2TtfContext,
+    fonts: HashMap<(String, u16), Rc<Font<'static, 'static>>>,
+    glyph_atlases: HashMap<(String, u16), Rc<RefCell<GlyphAtlas>>>,
+}
+
+impl FontCache {
+    pub fn new(ttf_context: &'static Sdl2TtfContext) -> Self {
+        Self {
+            ttf_context,
+            fonts: HashMap::new(),
+            glyph_atlases: HashMap::new(),
+        }
+    }
+
+    pub fn get_font(&mut self, font_path: &str, font_size: u16) -> Result<Rc<Font<'static, 'static>>, EngineError> {
+        let key = (font_path.to_string(), font_size);
+        if let Some(font) = self.fonts.get(&key) {
+            return Ok(Rc::clone(font));
+        }
+
+        let font = Rc::new(self.load_font(font_path, font_size)?);
+        self.fonts.insert(key, Rc::clone(&font));
+        Ok(font)
+    }
+
+    pub fn get_glyph_atlas(&mut self, font_path: &str, font_size: u16) -> Result<Rc<RefCell<GlyphAtlas>>, EngineError> {
+        let key = (font_path.to_string(), font_size);
+        if let Some(glyph_atlas) = self.glyph_atlases.get(&key) {
+            return Ok(Rc::clone(glyph_atlas));
+        }
+
+        let glyph_atlas = Rc::new(RefCell::new(GlyphAtlas::new(font_path, self.get_font(font_path, font_size)?)));
+        self.glyph_atlases.insert(key, Rc::clone(&glyph_atlas));
+        Ok(glyph_atlas)
+    }
+
+    // 不經過快取直接讀取，比較快取前後的耗時時使用
+    pub fn load_font(&self, font_path: &str, font_size: u16) -> Result<Font<'static, 'static>, EngineError> {
+        self.ttf_context.load_font(Path::new(font_path), font_size)
+            .map_err(|message| EngineError::Font { path: font_path.to_string(), message })
+    }
+}
@@ -1,26 +1,52 @@
 use sdl2::ttf::Sdl2TtfContext;
+use crate::engine::drawable_implements::font_cache::FontCache;
+use crate::engine::asset_manager::AssetManager;
+use crate::engine::engine_error::EngineError;
 use crate::engine::drawable_implements::plane::Plane;
 use crate::engine::drawable_implements::text::Text;
+use crate::engine::rendering::shader_registry::{ShaderProgram, ShaderRegistry};
+use crate::engine::rendering::texture::create_placeholder_texture;
+use std::cell::RefCell;
 use std::rc::Rc;
 
+// 直接給 shader 原始碼，或使用已在 ShaderRegistry 以名稱註冊的 shader
+pub enum ShaderSource<'a> {
+    Code(&'a str, &'a str),
+    Name(&'a str),
+}
+
 pub struct DrawableGenerator {
-    ttf_context: Rc<Sdl2TtfContext>,
+    pub font_cache: RefCell<FontCache>,
+    pub asset_manager: RefCell<AssetManager>,
+    pub shader_registry: RefCell<ShaderRegistry>,
 }
 
 impl DrawableGenerator {
-    pub fn new(ttf_context: Rc<Sdl2TtfContext>) -> Self {
-        Self { ttf_context }
+    pub fn new(ttf_context: &'static Sdl2TtfContext) -> Self {
+        Self {
+            font_cache: RefCell::new(FontCache::new(ttf_context)),
+            asset_manager: RefCell::new(AssetManager::new()),
+            shader_registry: RefCell::new(ShaderRegistry::new()),
+        }
     }
 
+    // 缺少的素材在下層已用替代品處理，會失敗的只剩 shader 這類程式錯誤
     pub fn generate_plane_from_image(
         &self,
         rect: (f32, f32, f32, f32),
         z_index: f32,
         color: (f32, f32, f32, f32),
         image_path: Option<&String>,
-        vertex_shader: &str,
-        fragment_shader: &str) -> Plane {
-        Plane::new_from_image(rect, z_index, color, image_path, vertex_shader, fragment_shader)
+        shader_source: ShaderSource) -> Result<Plane, EngineError> {
+        let shader_program = self.get_shader_program(shader_source)?;
+        // 圖片讀取失敗時以替代貼圖顯示，不中斷遊戲
+        let texture = image_path.map(|image_path| {
+            self.asset_manager.borrow_mut().load_texture(image_path).unwrap_or_else(|error| {
+                eprintln!("{}", error);
+                Rc::new(create_placeholder_texture())
+            })
+        });
+        Ok(Plane::new(rect, z_index, color, texture, shader_program))
     }
 
     pub fn generate_text(
@@ -32,18 +58,31 @@ impl DrawableGenerator {
         color: (f32, f32, f32, f32),
         font_path: &str,
         font_size: u16,
-        vertex_shader: &str,
-        fragment_shader: &str) -> Text {
-        Text::new(
+        shader_source: ShaderSource) -> Result<Text, EngineError> {
+        Ok(Text::new(
             left_bottom,
             z_index,
             content,
             end_range_ratio,
             color,
-            &self.ttf_context,
-            font_path,
-            font_size,
-            vertex_shader,
-            fragment_shader)
+            self.font_cache.borrow_mut().get_glyph_atlas(font_path, font_size),
+            self.get_shader_program(shader_source)?))
+    }
+
+    fn get_shader_program(&self, shader_source: ShaderSource) -> Result<Rc<ShaderProgram>, EngineError> {
+        match shader_source {
+            ShaderSource::Code(vertex_shader, fragment_shader) =>
+                self.shader_registry.borrow_mut().get_or_compile(vertex_shader, fragment_shader),
+            ShaderSource::Name(name) => self.shader_registry.borrow().get(name),
+        }
+    }
+
+    pub fn measure_text(&self, content: &str, font_path: &str, font_size: u16) -> (f32, f32) {
+        self.font_cache.borrow_mut().get_glyph_atlas(font_path, font_size)
+            .and_then(|glyph_atlas| glyph_atlas.borrow_mut().measure(content))
+            .unwrap_or_else(|error| {
+                eprintln!("{}", error);
+                (0.0, 0.0)
+            })
     }
 }
\ No newline at end of file
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use sdl2::ttf::Font;
+use crate::engine::engine_error::EngineError;
+use crate::engine::rendering::texture::{create_empty_texture, update_texture_from_surface, Texture};
+
+const ATLAS_PAGE_SIZE: u32 = 1024;
+// 字與字之間留空，避免線性取樣時取到旁邊的字
+const GLYPH_PADDING: u32 = 1;
+
+#[derive(Clone, Copy)]
+struct Glyph {
+    // 空白字元不需要貼圖
+    page_index: Option<usize>,
+    tex_rect: (f32, f32, f32, f32),
+    size: (f32, f32),
+    advance: f32,
+}
+
+// 相對於文字左下角的位置
+pub struct GlyphQuad {
+    pub page_index: usize,
+    pub rect: (f32, f32, f32, f32),
+    pub tex_rect: (f32, f32, f32, f32),
+}
+
+pub struct TextLayout {
+    // 每個字一項，換行與空白沒有四邊形
+    pub glyph_quads: Vec<Option<GlyphQuad>>,
+    pub size: (f32, f32),
+}
+
+struct AtlasPage {
+    texture: Rc<Texture>,
+    cursor: (u32, u32),
+    row_height: u32,
+}
+
+impl AtlasPage {
+    fn new() -> Self {
+        Self {
+            texture: Rc::new(create_empty_texture(ATLAS_PAGE_SIZE, ATLAS_PAGE_SIZE)),
+            cursor: (0, 0),
+            row_height: 0,
+        }
+    }
+
+    // 由左至右、由上至下一列一列擺放
+    fn allocate(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        if self.cursor.0 + width > ATLAS_PAGE_SIZE {
+            self.cursor = (0, self.cursor.1 + self.row_height);
+            self.row_height = 0;
+        }
+        if self.cursor.1 + height > ATLAS_PAGE_SIZE {
+            return None;
+        }
+
+        let position = self.cursor;
+        self.cursor.0 += width;
+        self.row_height = self.row_height.max(height);
+        Some(position)
+    }
+}
+
+// 同一個字型與大小的字只光柵化一次，放進共用的貼圖頁
+pub struct GlyphAtlas {
+    font_path: String,
+    font: Rc<Font<'static, 'static>>,
+    pages: Vec<AtlasPage>,
+    glyphs: HashMap<char, Glyph>,
+}
+
+impl GlyphAtlas {
+    pub fn new(font_path: &str, font: Rc<Font<'static, 'static>>) -> Self {
+        Self {
+            font_path: font_path.to_string(),
+            font,
+            pages: vec![],
+            glyphs: HashMap::new(),
+        }
+    }
+
+    pub fn get_page_texture(&self, page_index: usize) -> Rc<Texture> {
+        Rc::clone(&self.pages[page_index].texture)
+    }
+
+    pub fn layout(&mut self, content: &str) -> Result<TextLayout, EngineError> {
+        let line_height = self.font.height() as f32;
+        let line_spacing = self.font.recommended_line_spacing() as f32;
+        let line_count = content.split('\n').count();
+        let height = (line_count - 1) as f32 * line_spacing + line_height;
+
+        let mut glyph_quads = vec![];
+        let mut pen_position = (0.0, height - line_height);
+        let mut width: f32 = 0.0;
+        for character in content.chars() {
+            if character == '\n' {
+                pen_position = (0.0, pen_position.1 - line_spacing);
+                glyph_quads.push(None);
+                continue;
+            }
+
+            let glyph = self.get_glyph(character)?;
+            glyph_quads.push(glyph.page_index.map(|page_index| GlyphQuad {
+                page_index,
+                rect: (pen_position.0, pen_position.1, glyph.size.0, glyph.size.1),
+                tex_rect: glyph.tex_rect,
+            }));
+            pen_position.0 += glyph.advance;
+            width = width.max(pen_position.0);
+        }
+
+        Ok(TextLayout {
+            glyph_quads,
+            size: (width, height),
+        })
+    }
+
+    pub fn measure(&mut self, content: &str) -> Result<(f32, f32), EngineError> {
+        self.layout(content).map(|text_layout| text_layout.size)
+    }
+
+    fn get_glyph(&mut self, character: char) -> Result<Glyph, EngineError> {
+        if let Some(glyph) = self.glyphs.get(&character) {
+            return Ok(*glyph);
+        }
+
+        let glyph = if character.is_whitespace() {
+            let advance = self.font.find_glyph_metrics(character)
+                .map_or(self.font.height() / 2, |glyph_metrics| glyph_metrics.advance);
+            Glyph {
+                page_index: None,
+                tex_rect: (0.0, 0.0, 0.0, 0.0),
+                size: (0.0, 0.0),
+                advance: advance as f32,
+            }
+        } else {
+            self.rasterize_glyph(character)?
+        };
+        self.glyphs.insert(character, glyph);
+        Ok(glyph)
+    }
+
+    fn rasterize_glyph(&mut self, character: char) -> Result<Glyph, EngineError> {
+        let surface = self.font.render_char(character)
+            .blended(sdl2::pixels::Color::RGBA(255, 255, 255, 255))
+            .map_err(|error| EngineError::Font { path: self.font_path.clone(), message: error.to_string() })?;
+        let width = surface.width();
+        let height = surface.height();
+        let advance = self.font.find_glyph_metrics(character)
+            .map_or(width as i32, |glyph_metrics| glyph_metrics.advance);
+
+        let (page_index, position) = self.allocate(width, height)?;
+        update_texture_from_surface(&self.pages[page_index].texture, position, surface)?;
+
+        let page_size = ATLAS_PAGE_SIZE as f32;
+        Ok(Glyph {
+            page_index: Some(page_index),
+            tex_rect: (
+                position.0 as f32 / page_size,
+                position.1 as f32 / page_size,
+                width as f32 / page_size,
+                height as f32 / page_size),
+            size: (width as f32, height as f32),
+            advance: advance as f32,
+        })
+    }
+
+    fn allocate(&mut self, width: u32, height: u32) -> Result<(usize, (u32, u32)), EngineError> {
+        let padded_width = width + GLYPH_PADDING;
+        let padded_height = height + GLYPH_PADDING;
+        if padded_width > ATLAS_PAGE_SIZE || padded_height > ATLAS_PAGE_SIZE {
+            return Err(EngineError::Font {
+                path: self.font_path.clone(),
+                message: format!("Glyph of {}x{} doesn't fit in an atlas page", width, height),
+            });
+        }
+
+        if let Some(position) = self.pages.last_mut().and_then(|page| page.allocate(padded_width, padded_height)) {
+            return Ok((self.pages.len() - 1, position));
+        }
+
+        // 中日韓文字的字數很多，目前的頁面放滿時再新增一頁
+        let mut page = AtlasPage::new();
+        let position = page.allocate(padded_width, padded_height)
+            .expect("An empty atlas page must fit a glyph smaller than the page");
+        self.pages.push(page);
+        Ok((self.pages.len() - 1, position))
+    }
+}
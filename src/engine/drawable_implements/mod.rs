@@ -1,3 +1,6 @@
 pub(crate) mod plane;
 pub(crate) mod text;
-pub(crate) mod generator;
\ No newline at end of file
+pub(crate) mod generator;
+pub(crate) mod font_cache;
+pub(crate) mod glyph_atlas;
+pub(crate) mod text_benchmark;
\ No newline at end of file
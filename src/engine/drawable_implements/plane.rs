@@ -1,108 +1,92 @@
+use std::any::Any;
+use std::rc::Rc;
 use nalgebra_glm::Mat4;
-use sdl2::image::LoadSurface;
 use crate::engine::drawable_component::DrawableComponent;
 use crate::engine::component::Component;
-use crate::engine::rendering::mesh::Mesh;
+use crate::engine::transform::Transform;
 use crate::engine::rendering::material::Material;
-use crate::engine::rendering::drawable_object::DrawableObject;
+use crate::engine::rendering::shader_registry::ShaderProgram;
+use crate::engine::rendering::sprite_batch::{QuadDescriptor, SpriteBatch, VERTICES_PER_QUAD};
+use crate::engine::rendering::texture::Texture;
 
 pub struct Plane {
-    pub drawable: DrawableObject
+    pub transform: Transform,
+    material: Material,
+    rect: (f32, f32, f32, f32),
+    z_index: f32,
+    vertex_colors: [(f32, f32, f32, f32); VERTICES_PER_QUAD],
+    tint: (f32, f32, f32, f32),
 }
 
 impl Plane {
-    pub fn new_from_image(
+    pub fn new(
         rect: (f32, f32, f32, f32),
         z_index: f32,
         color: (f32, f32, f32, f32),
-        image_path: Option<&String>,
-        vertex_shader: &str,
-        fragment_shader: &str) -> Self {
-        let mesh = Mesh {
-            vertices: vec![
-                rect.0, rect.1, z_index,
-                rect.0 + rect.2, rect.1, z_index,
-                rect.0 + rect.2, rect.1 + rect.3, z_index,
-                rect.0, rect.1 + rect.3, z_index,
-            ],
-            tex_coords: vec![
-                0.0, 1.0,
-                1.0, 1.0,
-                1.0, 0.0,
-                0.0, 0.0,
-            ],
-            indices: vec![0, 1, 2, 2, 3, 0],
-        };
-
-        let texture_id = image_path.map(load_texture_from_image);
-        let material = Material::new(vec![
-                color.0, color.1, color.2, color.3,
-                color.0, color.1, color.2, color.3,
-                color.0, color.1, color.2, color.3,
-                color.0, color.1, color.2, color.3,
-            ],
-            texture_id,
-            vertex_shader,
-            fragment_shader);
-
+        texture: Option<Rc<Texture>>,
+        shader_program: Rc<ShaderProgram>) -> Self {
         Self {
-            drawable: DrawableObject::new(mesh, material),
+            transform: Transform::new(),
+            material: Material::new(texture, shader_program),
+            rect,
+            z_index,
+            vertex_colors: [(1.0, 1.0, 1.0, 1.0); VERTICES_PER_QUAD],
+            tint: color,
         }
     }
 
-    pub fn set_alpha(&mut self, alpha: f32) {
-        let color = &self.drawable.material.color;
-        self.drawable.set_color(vec![
-            color[0], color[1], color[2], alpha,
-            color[0], color[1], color[2], alpha,
-            color[0], color[1], color[2], alpha,
-            color[0], color[1], color[2], alpha,
-        ]);
+    pub fn set_rect(&mut self, rect: (f32, f32, f32, f32)) {
+        self.rect = rect;
     }
-}
 
-fn load_texture_from_image(image_path: &String) -> u32 {
-    let surface = sdl2::surface::Surface::from_file(image_path).unwrap();
-    let surface = surface.convert_format(sdl2::pixels::PixelFormatEnum::RGBA32).unwrap();
-    let width = surface.width();
-    let height = surface.height();
-    let surface_pixels = surface.without_lock().unwrap();
+    // 依左下、右下、右上、左上的順序，和 tint 相乘
+    pub fn set_vertex_colors(&mut self, vertex_colors: [(f32, f32, f32, f32); VERTICES_PER_QUAD]) {
+        self.vertex_colors = vertex_colors;
+    }
+
+    pub fn get_alpha(&self) -> f32 {
+        self.tint.3
+    }
 
-    let mut texture_id: u32 = 0;
-    unsafe {
-        gl::GenTextures(1, &mut texture_id);
-        gl::BindTexture(gl::TEXTURE_2D, texture_id);
-        gl::TexImage2D(
-            gl::TEXTURE_2D,
-            0,
-            gl::RGBA as i32,
-            width as i32,
-            height as i32,
-            0,
-            gl::RGBA,
-            gl::UNSIGNED_BYTE,
-            surface_pixels.as_ptr() as *const std::os::raw::c_void,
-        );
+    pub fn set_alpha(&mut self, alpha: f32) {
+        self.tint.3 = alpha;
+    }
 
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+    pub fn get_color(&self) -> (f32, f32, f32, f32) {
+        self.tint
     }
 
-    texture_id
+    pub fn set_color(&mut self, color: (f32, f32, f32, f32)) {
+        self.tint = color;
+    }
 }
 
 impl Component for Plane {
-    fn update(&mut self) { /* Empty */ }
+    fn update(&mut self, _delta_time: f32) { /* Empty */ }
 
     fn as_drawable(&self) -> Option<&dyn DrawableComponent> {
         Some(self)
     }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
 }
 
 impl DrawableComponent for Plane {
-    fn draw(&self, projection_matrix: Mat4) {
-        self.drawable.draw(projection_matrix);
+    fn draw_with_transform(&self, sprite_batch: &mut SpriteBatch, parent_matrix: &Mat4) {
+        sprite_batch.add_quad(&QuadDescriptor {
+            material: &self.material,
+            model_matrix: &(parent_matrix * self.transform.get_model_matrix(self.rect)),
+            rect: self.rect,
+            z_index: self.z_index,
+            tex_rect: (0.0, 0.0, 1.0, 1.0),
+            vertex_colors: &self.vertex_colors,
+            tint: self.tint,
+        });
     }
 }
@@ -1,129 +1,115 @@
-use std::ops::{Index, Range};
-use std::path::Path;
+use std::any::Any;
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
 use nalgebra_glm::Mat4;
-use sdl2::surface::Surface;
-use sdl2::ttf::Font;
 use crate::engine::component::Component;
 use crate::engine::drawable_component::DrawableComponent;
-use crate::engine::rendering::drawable_object::DrawableObject;
+use crate::engine::drawable_implements::glyph_atlas::{GlyphAtlas, GlyphQuad, TextLayout};
+use crate::engine::engine_error::EngineError;
 use crate::engine::rendering::material::Material;
-use crate::engine::rendering::mesh::Mesh;
+use crate::engine::rendering::shader_registry::ShaderProgram;
+use crate::engine::transform::Transform;
+use crate::engine::rendering::sprite_batch::{QuadDescriptor, SpriteBatch, VERTICES_PER_QUAD};
+
+// 每張用到的貼圖頁一個材質
+struct TextPage {
+    page_index: usize,
+    material: Material,
+}
 
 pub struct Text {
-    pub drawable: DrawableObject,
+    pub transform: Transform,
     pub content: String,
     pub end_range_ratio: f32,
+    tint: (f32, f32, f32, f32),
+    glyph_colors: Vec<(f32, f32, f32, f32)>,
+    pages: Vec<TextPage>,
 
     // static
-    font_path: String,
-    font_size: u16,
+    glyph_quads: Vec<Option<GlyphQuad>>,
+    left_bottom: (f32, f32),
+    z_index: f32,
+    size: (f32, f32),
 }
 
 impl Text {
     pub fn new(
-        left_top: (f32, f32),
+        left_bottom: (f32, f32),
         z_index: f32,
         content: &String,
         end_range_ratio: f32,
         color: (f32, f32, f32, f32),
-        ttf_context: &sdl2::ttf::Sdl2TtfContext,
-        font_path: &str,
-        font_size: u16,
-        vertex_shader: &str,
-        fragment_shader: &str) -> Self {
-
-        let range_end = if end_range_ratio >= 1.0 {
-            content.len()
-        } else {
-            content.char_indices().nth((content.char_indices().count() as f32 * end_range_ratio).trunc() as usize).unwrap().0
-        };
-        let font = ttf_context.load_font(Path::new(&font_path), font_size).unwrap();
-        let surface = font.render(if range_end <= 0 { " " } else { &content[..range_end] })
-            .blended(sdl2::pixels::Color::RGBA(255, 255, 255, 255))
-            .unwrap();
-
-        let text_width = surface.width() as f32;
-        let text_height = surface.height() as f32;
-
-        let texture_id = create_texture_from_surface(surface);
-
-        let mesh = Mesh {
-            vertices: vec![
-                left_top.0, left_top.1, z_index,
-                left_top.0 + text_width, left_top.1, z_index,
-                left_top.0 + text_width, left_top.1 + text_height, z_index,
-                left_top.0, left_top.1 + text_height, z_index,
-            ],
-            tex_coords: vec![
-                0.0, 1.0,
-                1.0, 1.0,
-                1.0, 0.0,
-                0.0, 0.0,
-            ],
-            indices: vec![0, 1, 2, 2, 3, 0],
-        };
-
-        let material = Material::new(vec![
-            color.0, color.1, color.2, color.3,
-            color.0, color.1, color.2, color.3,
-            color.0, color.1, color.2, color.3,
-            color.0, color.1, color.2, color.3,
-        ], Some(texture_id), vertex_shader, fragment_shader);
+        glyph_atlas: Result<Rc<RefCell<GlyphAtlas>>, EngineError>,
+        shader_program: Rc<ShaderProgram>) -> Self {
+
+        // 字型讀取失敗時不顯示文字，但遊戲可以繼續
+        let glyph_atlas = glyph_atlas
+            .map_err(|error| eprintln!("{}", error))
+            .ok();
+        let TextLayout { glyph_quads, size } =
+            match glyph_atlas.as_ref().map(|glyph_atlas| glyph_atlas.borrow_mut().layout(content)) {
+                Some(Ok(text_layout)) => text_layout,
+                Some(Err(error)) => {
+                    eprintln!("{}", error);
+                    TextLayout { glyph_quads: vec![], size: (0.0, 0.0) }
+                },
+                None => TextLayout { glyph_quads: vec![], size: (0.0, 0.0) },
+            };
+
+        let mut page_indices = glyph_quads.iter().flatten().map(|glyph_quad| glyph_quad.page_index).collect::<Vec<_>>();
+        page_indices.sort();
+        page_indices.dedup();
+        let pages = page_indices
+            .into_iter()
+            .map(|page_index| {
+                let texture = glyph_atlas.as_ref().map(|glyph_atlas| glyph_atlas.borrow().get_page_texture(page_index));
+                TextPage {
+                    page_index,
+                    material: Material::new(texture, Rc::clone(&shader_program)),
+                }
+            })
+            .collect();
 
         Self {
-            drawable: DrawableObject::new(mesh, material),
+            transform: Transform::new(),
             content: content.to_string(),
             end_range_ratio,
-            font_path: font_path.to_string(),
-            font_size,
+            tint: color,
+            glyph_colors: vec![(1.0, 1.0, 1.0, 1.0); content.chars().count()],
+            pages,
+            glyph_quads,
+            left_bottom,
+            z_index,
+            size,
         }
     }
 
+    pub fn get_alpha(&self) -> f32 {
+        self.tint.3
+    }
+
     pub fn set_alpha(&mut self, alpha: f32) {
-        let color = &self.drawable.material.color;
-        self.drawable.set_color(vec![
-            color[0], color[1], color[2], alpha,
-            color[0], color[1], color[2], alpha,
-            color[0], color[1], color[2], alpha,
-            color[0], color[1], color[2], alpha,
-        ]);
+        self.tint.3 = alpha;
+    }
+
+    pub fn get_color(&self) -> (f32, f32, f32, f32) {
+        self.tint
     }
 
     pub fn set_color(&mut self, color: (f32, f32, f32, f32)) {
-        self.drawable.set_color(vec![
-            color.0, color.1, color.2, color.3,
-            color.0, color.1, color.2, color.3,
-            color.0, color.1, color.2, color.3,
-            color.0, color.1, color.2, color.3,
-        ]);
-    }
-
-    pub fn set_range(&mut self,
-                     ttf_context: &sdl2::ttf::Sdl2TtfContext,
-                     end_range_ratio: f32){
-        let range_end = if end_range_ratio >= 1.0 {
-            self.content.len()
-        } else {
-            self.content.char_indices().nth((self.content.char_indices().count() as f32 * end_range_ratio).trunc() as usize).unwrap().0
-        };
-        let font = ttf_context.load_font(Path::new(&self.font_path), self.font_size).unwrap();
-        let surface = font.render(if range_end <= 0 { " " } else { &self.content[..range_end] })
-            .blended(sdl2::pixels::Color::RGBA(255, 255, 255, 255))
-            .unwrap();
-        let text_width = surface.width() as f32;
-        let text_height = surface.height() as f32;
+        self.tint = color;
+    }
 
-        self.end_range_ratio = end_range_ratio;
-        self.drawable.set_texture(Some(create_texture_from_surface(surface)));
+    // 以字為單位上色，範圍是字的索引，會再和整段文字的顏色相乘
+    pub fn set_glyph_color(&mut self, glyph_range: Range<usize>, color: (f32, f32, f32, f32)) {
+        let glyph_range = glyph_range.start.min(self.glyph_colors.len())..glyph_range.end.min(self.glyph_colors.len());
+        self.glyph_colors[glyph_range].fill(color);
+    }
 
-        let left_top = (self.drawable.mesh.vertices[0], self.drawable.mesh.vertices[1]);
-        let z_index = self.drawable.mesh.vertices[2];
-        self.drawable.set_vertices(vec![
-            left_top.0, left_top.1, z_index,
-            left_top.0 + text_width, left_top.1, z_index,
-            left_top.0 + text_width, left_top.1 + text_height, z_index,
-            left_top.0, left_top.1 + text_height, z_index,
-        ]);
+    // 逐字顯示只需要調整每個字的透明度，不必重新產生貼圖
+    pub fn set_range(&mut self, end_range_ratio: f32) {
+        self.end_range_ratio = end_range_ratio;
     }
 
     pub fn get_content_char_indices_count(&self) -> usize {
@@ -131,56 +117,71 @@ impl Text {
     }
 
     pub fn contains(&self, position: (f32, f32)) -> bool {
-        position.0 >= self.drawable.mesh.vertices[0] &&
-        position.0 <= self.drawable.mesh.vertices[3] &&
-        position.1 >= self.drawable.mesh.vertices[1] &&
-        position.1 <= self.drawable.mesh.vertices[7]
+        self.contains_with_padding(position, 0.0)
     }
-}
 
-fn create_texture_from_surface(surface: sdl2::surface::Surface) -> u32 {
-    let surface = surface.convert_format(sdl2::pixels::PixelFormatEnum::RGBA32).unwrap();
-    let width = surface.width();
-    let height = surface.height();
-    let surface_pixels = surface.without_lock().unwrap();
-
-    let mut texture_id: u32 = 0;
-    unsafe {
-        gl::GenTextures(1, &mut texture_id);
-        gl::BindTexture(gl::TEXTURE_2D, texture_id);
-        gl::TexImage2D(
-            gl::TEXTURE_2D,
-            0,
-            gl::RGBA as i32,
-            width as i32,
-            height as i32,
-            0,
-            gl::RGBA,
-            gl::UNSIGNED_BYTE,
-            surface_pixels.as_ptr() as *const std::os::raw::c_void,
-        );
-
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
-    }
-
-    texture_id
+    pub fn contains_with_padding(&self, position: (f32, f32), padding: f32) -> bool {
+        position.0 >= self.left_bottom.0 - padding &&
+        position.0 <= self.left_bottom.0 + self.size.0 + padding &&
+        position.1 >= self.left_bottom.1 - padding &&
+        position.1 <= self.left_bottom.1 + self.size.1 + padding
+    }
+
+    // 顯示範圍邊緣的字依比例淡入，範圍外的字完全透明
+    fn get_glyph_tint(&self, glyph_index: usize) -> (f32, f32, f32, f32) {
+        let revealed_count = if self.end_range_ratio >= 1.0 {
+            self.glyph_colors.len() as f32
+        } else {
+            self.glyph_colors.len() as f32 * self.end_range_ratio
+        };
+        let reveal_alpha = (revealed_count - glyph_index as f32).clamp(0.0, 1.0);
+        (self.tint.0, self.tint.1, self.tint.2, self.tint.3 * reveal_alpha)
+    }
 }
 
 impl Component for Text {
-    fn update(&mut self) {
+    fn update(&mut self, _delta_time: f32) {
         /* Empty */
     }
 
     fn as_drawable(&self) -> Option<&dyn DrawableComponent> {
         Some(self)
     }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
 }
 
 impl DrawableComponent for Text {
-    fn draw(&self, projection_matrix: Mat4) {
-        self.drawable.draw(projection_matrix);
+    fn draw_with_transform(&self, sprite_batch: &mut SpriteBatch, parent_matrix: &Mat4) {
+        let model_matrix = parent_matrix *
+            self.transform.get_model_matrix((self.left_bottom.0, self.left_bottom.1, self.size.0, self.size.1));
+        for page in &self.pages {
+            for (glyph_index, glyph_quad) in self.glyph_quads.iter().enumerate() {
+                let Some(glyph_quad) = glyph_quad.as_ref().filter(|glyph_quad| glyph_quad.page_index == page.page_index) else {
+                    continue;
+                };
+                let tint = self.get_glyph_tint(glyph_index);
+                if tint.3 <= 0.0 {
+                    continue;
+                }
+
+                let (x, y, width, height) = glyph_quad.rect;
+                sprite_batch.add_quad(&QuadDescriptor {
+                    material: &page.material,
+                    model_matrix: &model_matrix,
+                    rect: (self.left_bottom.0 + x, self.left_bottom.1 + y, width, height),
+                    z_index: self.z_index,
+                    tex_rect: glyph_quad.tex_rect,
+                    vertex_colors: &[self.glyph_colors[glyph_index]; VERTICES_PER_QUAD],
+                    tint,
+                });
+            }
+        }
     }
 }
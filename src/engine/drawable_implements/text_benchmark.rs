@@ -0,0 +1,80 @@
+use sdl2::ttf::Font;
+use web_time::Instant;
+use crate::engine::drawable_implements::generator::{DrawableGenerator, ShaderSource};
+use crate::engine::engine_error::EngineError;
+use crate::engine::rendering::texture::{create_texture_from_surface, Texture};
+
+// 模擬逐字顯示，每幀增加顯示範圍並重新產生文字貼圖
+pub fn run_text_benchmark(
+    drawable_generator: &DrawableGenerator,
+    font_path: &str,
+    font_size: u16,
+    content: &String,
+    frame_count: u32,
+    vertex_shader: &str,
+    fragment_shader: &str) {
+    let frame_count = frame_count.max(1);
+    let get_end_range_ratio = |frame: u32| (frame + 1) as f32 / frame_count as f32;
+
+    // 改用字形圖集前：每幀重新讀取字型檔，並把整段文字光柵化成新的貼圖
+    let start_time = Instant::now();
+    for frame in 0..frame_count {
+        let text_texture = drawable_generator.font_cache.borrow()
+            .load_font(font_path, font_size)
+            .and_then(|font| render_text_surface_texture(&font, font_path, content, get_end_range_ratio(frame)));
+        if let Err(error) = text_texture {
+            eprintln!("{}", error);
+            return;
+        }
+    }
+    let uncached_seconds = start_time.elapsed().as_secs_f64();
+
+    // 改用字形圖集後：字只光柵化一次，之後只調整每個字的透明度
+    let mut text = match drawable_generator.generate_text(
+        (0.0, 0.0),
+        0.0,
+        content,
+        0.0,
+        (1.0, 1.0, 1.0, 1.0),
+        font_path,
+        font_size,
+        ShaderSource::Code(vertex_shader, fragment_shader)) {
+        Ok(text) => text,
+        Err(error) => {
+            eprintln!("{}", error);
+            return;
+        }
+    };
+    let start_time = Instant::now();
+    for frame in 0..frame_count {
+        text.set_range(get_end_range_ratio(frame));
+    }
+    let cached_seconds = start_time.elapsed().as_secs_f64();
+
+    println!("Text benchmark: {} frames, font {} ({}px)", frame_count, font_path, font_size);
+    println!("  per-string rasterization: {:.3} ms/frame", uncached_seconds * 1000.0 / frame_count as f64);
+    println!("  glyph atlas:              {:.3} ms/frame", cached_seconds * 1000.0 / frame_count as f64);
+}
+
+// 舊的文字繪製方式，只留給效能比較使用
+fn render_text_surface_texture(
+    font: &Font,
+    font_path: &str,
+    content: &str,
+    end_range_ratio: f32) -> Result<(Texture, f32, f32), EngineError> {
+    let range_end = if end_range_ratio >= 1.0 {
+        content.len()
+    } else {
+        content
+            .char_indices()
+            .nth((content.char_indices().count() as f32 * end_range_ratio).trunc() as usize)
+            .map_or(content.len(), |(index, _)| index)
+    };
+    let surface = font.render(if range_end == 0 { " " } else { &content[..range_end] })
+        .blended(sdl2::pixels::Color::RGBA(255, 255, 255, 255))
+        .map_err(|error| EngineError::Font { path: font_path.to_string(), message: error.to_string() })?;
+
+    let text_width = surface.width() as f32;
+    let text_height = surface.height() as f32;
+    Ok((create_texture_from_surface(surface)?, text_width, text_height))
+}
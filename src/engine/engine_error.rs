@@ -0,0 +1,30 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub enum EngineError {
+    Io { path: String, error: std::io::Error },
+    Sdl(String),
+    ImageDecode { path: String, message: String },
+    Font { path: String, message: String },
+    Audio { path: String, message: String },
+    ShaderCompile { log: String },
+    ShaderLink { log: String },
+    Gl(String),
+}
+
+impl Display for EngineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EngineError::Io { path, error } => write!(f, "Can't read {}: {}", path, error),
+            EngineError::Sdl(message) => write!(f, "SDL error: {}", message),
+            EngineError::ImageDecode { path, message } => write!(f, "Can't decode image {}: {}", path, message),
+            EngineError::Font { path, message } => write!(f, "Can't use font {}: {}", path, message),
+            EngineError::Audio { path, message } => write!(f, "Can't play audio {}: {}", path, message),
+            EngineError::ShaderCompile { log } => write!(f, "Shader compilation failed: {}", log),
+            EngineError::ShaderLink { log } => write!(f, "Shader linking failed: {}", log),
+            EngineError::Gl(message) => write!(f, "OpenGL error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for EngineError {}
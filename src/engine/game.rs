@@ -5,73 +5,151 @@ extern crate emscripten_main_loop;
 
 use std::rc::Rc;
 use std::cell::RefCell;
-use sdl2::video::Window;
+use std::collections::{HashMap, HashSet, VecDeque};
+use sdl2::controller::GameController;
+use sdl2::event::{Event, WindowEvent};
+use sdl2::mouse::{Cursor, SystemCursor};
+use sdl2::{GameControllerSubsystem, TimerSubsystem};
+use sdl2::video::{FullscreenType, SwapInterval, Window};
 use sdl2::Sdl;
 use sdl2::ttf::Sdl2TtfContext;
 use sdl2::video::GLContext;
 use crate::engine::audio_manager::AudioManager;
+use crate::engine::clock::Clock;
+use crate::engine::design_resolution::DesignResolution;
+use crate::engine::drawable_component::DrawableComponent;
+use crate::engine::engine_error::EngineError;
 use crate::engine::drawable_implements::generator::DrawableGenerator;
+use crate::engine::drawable_implements::plane::Plane;
+use crate::engine::rendering::sprite_batch::SpriteBatch;
+use super::input::binding::{Binding, InputBindings};
+use super::input::input_manager::InputManager;
+use super::input::input_state::InputState;
+use super::input::touch_gesture::{TouchGesture, TouchGestureEvent, TouchGestureRecognizer};
 use super::scene::Scene;
+use super::transition::Transition;
 use glm::Mat4;
 use glm::ortho;
-use std::time::{SystemTime};
+use std::time::Duration;
+use web_time::Instant;
 
+const INPUT_BINDINGS_PATH: &str = "./resources/configs/input_bindings.cfg";
+const TOUCH_MOUSE_ID: u32 = u32::MAX;
+pub const DEFAULT_SHADER_NAME: &str = "default";
+const TRANSITION_Z_INDEX: f32 = -0.99;
+const DEFAULT_TARGET_FPS: u32 = 60;
+// 視窗拖曳或中斷點造成的長時間停頓不要一次補完
+const MAX_DELTA_TIME: f32 = 0.25;
+// 以 DEFAULT_SHADER_NAME 註冊的預設 shader
+pub const VERTEX_SHADER: &str = include_str!("../shaders/vertex_shader.glsl");
+pub const FRAGMENT_SHADER: &str = include_str!("../shaders/fragment_shader.glsl");
 
-#[cfg(target_arch = "wasm32")]
-extern "C" {
-    fn emscripten_get_now() -> f64;
+enum SceneRequest {
+    Push(Rc<RefCell<dyn Scene>>),
+    Pop,
+    Change(Rc<RefCell<dyn Scene>>),
+}
+
+struct ActiveTransition {
+    transition: Transition,
+    elapsed_time: f32,
+    scene_request: Option<SceneRequest>,
+}
+
+fn setup_orthographic_projection(design_resolution: DesignResolution) -> Mat4 {
+    let (design_width, design_height) = design_resolution.get_size();
+    ortho(0.0, design_width, 0.0, design_height, 1.0, -1.0)
 }
 
-fn setup_orthographic_projection() -> Mat4 {
-    ortho(0.0, 1920.0, 0.0, 1080.0, 1.0, -1.0)
+// 保持設計解析度的比例置中，多出來的部分留黑邊
+fn calculate_letterbox_viewport(
+    design_resolution: DesignResolution,
+    window_width: i32,
+    window_height: i32) -> (i32, i32, i32, i32) {
+    let (design_width, design_height) = design_resolution.get_size();
+    let scale = (window_width as f32 / design_width).min(window_height as f32 / design_height);
+    let viewport_width = (design_width * scale).round() as i32;
+    let viewport_height = (design_height * scale).round() as i32;
+    (
+        (window_width - viewport_width) / 2,
+        (window_height - viewport_height) / 2,
+        viewport_width,
+        viewport_height,
+    )
 }
 
-fn update_viewport(window_width: i32, window_height: i32) {
+fn update_viewport(viewport: (i32, i32, i32, i32)) {
     unsafe {
-        gl::Viewport(0, 0, window_width, window_height);
+        gl::Viewport(viewport.0, viewport.1, viewport.2, viewport.3);
     }
 }
 
 pub struct Game {
-    title: String,
     pub width: u32,
     pub height: u32,
-    current_scene: Option<Rc<RefCell<dyn Scene>>>,
+    scene_stack: Vec<Rc<RefCell<dyn Scene>>>,
+    scene_requests: VecDeque<(SceneRequest, Transition)>,
+    active_transition: Option<ActiveTransition>,
+    transition_plane: Plane,
     pub drawable_generator: DrawableGenerator,
     pub audio_manager: AudioManager<'static>,
     pub current_projection_matrix: Mat4,
+    pub sprite_batch: SpriteBatch,
+    design_resolution: DesignResolution,
+    viewport: (i32, i32, i32, i32),
+    input_manager: InputManager,
+    touch_gesture_recognizer: TouchGestureRecognizer,
+    requested_cursor: SystemCursor,
+    current_cursor: Option<(SystemCursor, Cursor)>,
+    is_quit_requested: bool,
+    target_frame_duration: Option<Duration>,
+    fixed_time_step: Option<f32>,
+    accumulated_time: f32,
+    interpolation_alpha: f32,
+    is_input_consumed: bool,
+    is_texture_logging: bool,
+    clock: Box<dyn Clock>,
+    last_updated_time: f64,
 
     // Native Part
     sdl_context: Sdl,
+    game_controller_subsystem: GameControllerSubsystem,
+    game_controllers: HashMap<u32, GameController>,
+    timer_subsystem: TimerSubsystem,
     window: Window,
-    gl_context: GLContext,
-    pub sdl2_ttf_context: Rc<Sdl2TtfContext>,
-    #[cfg(target_arch = "wasm32")]
-    last_updated_time: f64,
-    #[cfg(not(target_arch = "wasm32"))]
-    last_updated_time: SystemTime,
+    // 只需要存活到遊戲結束，context 釋放後就無法繪製
+    _gl_context: GLContext,
 }
 
 impl Game {
-    pub fn new(title: &str, width: u32, height: u32, is_web: bool) -> Self {
-        let sdl_context = sdl2::init().unwrap();
-        let video_subsystem = sdl_context.video().unwrap();
+    pub fn new(
+        title: &str,
+        width: u32,
+        height: u32,
+        design_resolution: DesignResolution,
+        is_web: bool,
+        clock: Box<dyn Clock>) -> Result<Self, EngineError> {
+        let sdl_context = sdl2::init().map_err(EngineError::Sdl)?;
+        let video_subsystem = sdl_context.video().map_err(EngineError::Sdl)?;
+        // 已連接的手把會在啟動時收到 ControllerDeviceAdded，統一在事件中開啟
+        let game_controller_subsystem = sdl_context.game_controller().map_err(EngineError::Sdl)?;
+        let timer_subsystem = sdl_context.timer().map_err(EngineError::Sdl)?;
 
-        let mut audio_manager = AudioManager::new(is_web);
+        let audio_manager = AudioManager::new(is_web);
 
         let window = video_subsystem
             .window(title, width, height)
             .opengl()
             .resizable()
             .build()
-            .unwrap();
+            .map_err(|error| EngineError::Sdl(error.to_string()))?;
 
         let gl_attr = video_subsystem.gl_attr();
         gl_attr.set_context_profile(sdl2::video::GLProfile::GLES);
         gl_attr.set_context_version(3, 0);
 
-        let gl_context = window.gl_create_context().unwrap();
-        window.gl_make_current(&gl_context).unwrap();
+        let gl_context = window.gl_create_context().map_err(EngineError::Gl)?;
+        window.gl_make_current(&gl_context).map_err(EngineError::Gl)?;
         gl::load_with(|s| video_subsystem.gl_get_proc_address(s) as *const _);
 
         // OpenGL 的設置
@@ -79,38 +157,94 @@ impl Game {
             gl::Enable(gl::DEPTH_TEST);
             gl::Enable(gl::BLEND);
             gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
-            gl::Viewport(0, 0, width as i32, height as i32);
             gl::ClearColor(0.0, 0.0, 0.0, 1.0);
         }
+        let viewport = calculate_letterbox_viewport(design_resolution, width as i32, height as i32);
+        update_viewport(viewport);
 
-        let sdl2_ttf_context = Rc::new(sdl2::ttf::init().unwrap());
-        let drawable_generator = DrawableGenerator::new(Rc::clone(&sdl2_ttf_context));
-        let current_projection_matrix = setup_orthographic_projection();
+        // 字型快取需要整個程式期間都存在的 context
+        let sdl2_ttf_context: &'static Sdl2TtfContext =
+            Box::leak(Box::new(sdl2::ttf::init().map_err(|error| EngineError::Sdl(error.to_string()))?));
+        let drawable_generator = DrawableGenerator::new(sdl2_ttf_context);
+        let current_projection_matrix = setup_orthographic_projection(design_resolution);
+        let (design_width, design_height) = design_resolution.get_size();
+        drawable_generator.shader_registry
+            .borrow_mut()
+            .register(DEFAULT_SHADER_NAME, VERTEX_SHADER, FRAGMENT_SHADER)?;
+        let transition_plane = Plane::new(
+            (0.0, 0.0, design_width, design_height),
+            TRANSITION_Z_INDEX,
+            (0.0, 0.0, 0.0, 0.0),
+            None,
+            drawable_generator.shader_registry.borrow().get(DEFAULT_SHADER_NAME)?);
 
-        #[cfg(target_arch = "wasm32")]
-        let current_time = unsafe {
-            emscripten_get_now()
-        };
-
-        Self {
-            title: title.to_string(),
+        let mut game = Self {
             width,
             height,
-            current_scene: None,
+            scene_stack: Vec::new(),
+            scene_requests: VecDeque::new(),
+            active_transition: None,
+            transition_plane,
             drawable_generator,
             audio_manager,
             current_projection_matrix,
+            sprite_batch: SpriteBatch::new(),
+            design_resolution,
+            viewport,
+            input_manager: InputManager::new(InputBindings::load(INPUT_BINDINGS_PATH)),
+            touch_gesture_recognizer: TouchGestureRecognizer::new(),
+            requested_cursor: SystemCursor::Arrow,
+            current_cursor: None,
+            is_quit_requested: false,
+            target_frame_duration: None,
+            fixed_time_step: None,
+            accumulated_time: 0.0,
+            interpolation_alpha: 1.0,
+            is_input_consumed: true,
+            is_texture_logging: false,
+            last_updated_time: clock.now(),
+            clock,
             sdl_context,
+            game_controller_subsystem,
+            game_controllers: HashMap::new(),
+            timer_subsystem,
             window,
-            gl_context,
-            sdl2_ttf_context,
-            #[cfg(target_arch = "wasm32")]
-            last_updated_time: current_time,
-            #[cfg(not(target_arch = "wasm32"))]
-            last_updated_time: SystemTime::now(),
+            _gl_context: gl_context,
+        };
+        game.set_vsync(true);
+        game.set_target_fps(DEFAULT_TARGET_FPS);
+        Ok(game)
+    }
+
+    pub fn set_vsync(&mut self, is_vsync: bool) {
+        let swap_interval = if is_vsync { SwapInterval::VSync } else { SwapInterval::Immediate };
+        if let Err(error) = self.window.subsystem().gl_set_swap_interval(swap_interval) {
+            eprintln!("Can't set swap interval: {}", error);
         }
     }
 
+    // 0 表示不限制幀率，網頁版由瀏覽器控制幀率所以不會生效
+    pub fn set_target_fps(&mut self, target_fps: u32) {
+        self.target_frame_duration = (target_fps > 0).then(|| Duration::from_secs_f64(1.0 / target_fps as f64));
+    }
+
+    // 0 表示每幀更新一次，否則以固定的時間間隔更新場景
+    pub fn set_fixed_update_rate(&mut self, updates_per_second: u32) {
+        self.fixed_time_step = (updates_per_second > 0).then(|| 1.0 / updates_per_second as f32);
+        self.accumulated_time = 0.0;
+        self.interpolation_alpha = 1.0;
+    }
+
+    // 開啟後每次切換場景都會列出仍在使用的貼圖，方便追蹤沒有釋放的資源
+    pub fn set_texture_logging(&mut self, is_texture_logging: bool) {
+        self.is_texture_logging = is_texture_logging;
+    }
+
+    // 固定步進時，距離下一次更新的比例，繪製時可用來在前後兩次狀態間內插
+    pub fn get_interpolation_alpha(&self) -> f32 {
+        self.interpolation_alpha
+    }
+
     pub fn run(mut self) {
         #[cfg(target_arch = "wasm32")]
         {
@@ -123,33 +257,22 @@ impl Game {
             impl MainLoop for EngineLoop {
                 fn main_loop(&mut self) -> emscripten_main_loop::MainLoopEvent {
                     let mut game = self.game.borrow_mut();
-                    let mut hit_position: Option<(i32, i32)> = None;
-                    for event in game.sdl_context.event_pump().unwrap().poll_iter() {
-                        match event {
-                            sdl2::event::Event::Quit { .. } => return emscripten_main_loop::MainLoopEvent::Terminate,
-                            sdl2::event::Event::MouseButtonDown { x, y, .. } => {
-                                if game.audio_manager.is_started {
-                                    hit_position = Some((x * 1920 / game.width as i32, 1080 - y * 1080 / game.height as i32));
-                                }
-                                else{
-                                    game.audio_manager.start_music();
-                                }
-                            },
-                            sdl2::event::Event::Window { win_event, .. } => {
-                                if let sdl2::event::WindowEvent::Resized(window_width, window_height) = win_event {
-                                    update_viewport(window_width, window_height);
-                                    game.width = window_width as u32;
-                                    game.height = window_height as u32;
-                                    game.current_projection_matrix = setup_orthographic_projection();
-                                }
-                            }
-                            _ => {}
-                        }
+                    if game.is_input_consumed {
+                        game.input_manager.begin_frame();
+                    }
+                    let events = game.sdl_context.event_pump().unwrap().poll_iter().collect::<Vec<_>>();
+                    for event in events {
+                        game.handle_event(event);
                     }
+                    game.update_touch_gesture();
 
-                    game.update(hit_position);
+                    let input = game.input_manager.get_state();
+                    game.update(&input);
                     game.draw();
                     game.window.gl_swap_window();
+                    if game.is_quit_requested {
+                        return emscripten_main_loop::MainLoopEvent::Terminate;
+                    }
                     emscripten_main_loop::MainLoopEvent::Continue
                 }
             }
@@ -165,69 +288,383 @@ impl Game {
         {
             // Standalone 的遊戲循環
             let mut event_pump = self.sdl_context.event_pump().unwrap();
-            'running: loop {
-                let mut hit_position : Option<(i32, i32)> = None;
+            loop {
+                let frame_start_time = Instant::now();
+                if self.is_input_consumed {
+                    self.input_manager.begin_frame();
+                }
                 for event in event_pump.poll_iter() {
-                    match event {
-                        sdl2::event::Event::Quit { .. } => break 'running,
-                        sdl2::event::Event::MouseButtonDown { x, y, .. } => {
-                            hit_position = Some((x * 1920 / self.width as i32, 1080 - y * 1080 / self.height as i32));
-                        },
-                        sdl2::event::Event::Window { win_event, .. } => {
-                            if let sdl2::event::WindowEvent::Resized(window_width, window_height) = win_event {
-                                update_viewport(window_width, window_height);
-                                self.width = window_width as u32;
-                                self.height = window_height as u32;
-                                self.current_projection_matrix = setup_orthographic_projection();
-                            }
-                        },
-                        _ => {}
-                    }
+                    self.handle_event(event);
                 }
+                self.update_touch_gesture();
 
-                self.update(hit_position);
+                let input = self.input_manager.get_state();
+                self.update(&input);
                 self.draw();
                 self.window.gl_swap_window();
+                if self.is_quit_requested {
+                    break;
+                }
+                self.wait_for_next_frame(frame_start_time);
             }
         }
     }
 
-    fn update(&mut self, hit_position: Option<(i32, i32)>) {
-        #[cfg(target_arch = "wasm32")]
-        let current_time = unsafe {
-            emscripten_get_now()
-        };
-        #[cfg(not(target_arch = "wasm32"))]
-        let current_time = SystemTime::now();
+    fn handle_event(&mut self, event: Event) {
+        match event {
+            Event::Quit { .. } => self.is_quit_requested = true,
+            // 觸控產生的模擬滑鼠事件交給觸控處理
+            Event::MouseButtonDown { which: TOUCH_MOUSE_ID, .. } |
+            Event::MouseButtonUp { which: TOUCH_MOUSE_ID, .. } |
+            Event::MouseMotion { which: TOUCH_MOUSE_ID, .. } => {},
+            Event::MouseMotion { x, y, .. } => {
+                match self.to_design_position(x, y) {
+                    Some(design_position) => self.input_manager.move_pointer(design_position),
+                    None => self.input_manager.clear_pointer(),
+                }
+            },
+            Event::MouseButtonDown { mouse_btn, x, y, .. } => {
+                if self.audio_manager.is_started {
+                    // 點在黑邊上不算點擊
+                    if let Some(design_position) = self.to_design_position(x, y) {
+                        self.input_manager.set_hit_position(design_position);
+                        self.input_manager.press(Binding::Mouse(mouse_btn));
+                    }
+                }
+                else {
+                    self.audio_manager.start_music();
+                }
+            },
+            Event::MouseButtonUp { mouse_btn, .. } => {
+                self.input_manager.release(Binding::Mouse(mouse_btn));
+            },
+            Event::KeyDown { keycode: Some(keycode), repeat: false, .. } => {
+                self.input_manager.press(Binding::Key(keycode));
+            },
+            Event::KeyUp { keycode: Some(keycode), .. } => {
+                self.input_manager.release(Binding::Key(keycode));
+            },
+            Event::ControllerDeviceAdded { which, .. } => {
+                match self.game_controller_subsystem.open(which) {
+                    Ok(game_controller) => {
+                        self.game_controllers.insert(game_controller.instance_id(), game_controller);
+                    },
+                    Err(error) => eprintln!("Can't open game controller {}: {}", which, error),
+                }
+            },
+            Event::ControllerDeviceRemoved { which, .. } => {
+                self.game_controllers.remove(&which);
+            },
+            Event::ControllerButtonDown { button, .. } => {
+                self.input_manager.press(Binding::Controller(button));
+            },
+            Event::ControllerButtonUp { button, .. } => {
+                self.input_manager.release(Binding::Controller(button));
+            },
+            Event::FingerDown { finger_id, x, y, timestamp, .. } => {
+                self.input_manager.clear_pointer();
+                if self.audio_manager.is_started {
+                    self.touch_gesture_recognizer.finger_down(finger_id, (x, y), timestamp);
+                }
+                else {
+                    self.audio_manager.start_music();
+                }
+            },
+            Event::FingerMotion { finger_id, x, y, .. } => {
+                self.touch_gesture_recognizer.finger_motion(finger_id, (x, y));
+            },
+            Event::FingerUp { finger_id, x, y, timestamp, .. } => {
+                let touch_gesture_event = self.touch_gesture_recognizer.finger_up(finger_id, (x, y), timestamp);
+                self.apply_touch_gesture_event(touch_gesture_event);
+            },
+            Event::Window { win_event: WindowEvent::Resized(window_width, window_height), .. } |
+            Event::Window { win_event: WindowEvent::SizeChanged(window_width, window_height), .. } => {
+                self.viewport = calculate_letterbox_viewport(self.design_resolution, window_width, window_height);
+                update_viewport(self.viewport);
+                self.width = window_width as u32;
+                self.height = window_height as u32;
+                self.current_projection_matrix = setup_orthographic_projection(self.design_resolution);
+            },
+            _ => {}
+        }
+    }
 
-        #[cfg(target_arch = "wasm32")]
-        let delta_time = ((current_time - self.last_updated_time) / 1000.0) as f32;
-        #[cfg(not(target_arch = "wasm32"))]
-        let delta_time = self.last_updated_time.elapsed().unwrap().as_secs_f32();
+    fn update_touch_gesture(&mut self) {
+        let touch_gesture_event = self.touch_gesture_recognizer.update(self.timer_subsystem.ticks());
+        self.apply_touch_gesture_event(touch_gesture_event);
+    }
+
+    fn apply_touch_gesture_event(&mut self, touch_gesture_event: Option<TouchGestureEvent>) {
+        match touch_gesture_event {
+            Some(TouchGestureEvent::Pressed(touch_gesture, position)) => {
+                let window_position = ((position.0 * self.width as f32) as i32, (position.1 * self.height as f32) as i32);
+                let design_position = self.to_design_position(window_position.0, window_position.1);
+                // 點擊需要落在遊戲畫面內，滑動等手勢則不限位置
+                if touch_gesture == TouchGesture::Tap && design_position.is_none() {
+                    return;
+                }
+                if let Some(design_position) = design_position {
+                    self.input_manager.set_hit_position(design_position);
+                }
+                self.input_manager.press(Binding::Touch(touch_gesture));
+                // 長按要持續到手指放開，其他手勢都是瞬間完成
+                if touch_gesture != TouchGesture::LongPress {
+                    self.input_manager.release(Binding::Touch(touch_gesture));
+                }
+            },
+            Some(TouchGestureEvent::Released(touch_gesture)) => {
+                self.input_manager.release(Binding::Touch(touch_gesture));
+            },
+            None => {}
+        }
+    }
 
-        if let Some(scene) = self.current_scene.take() {
-            scene.borrow_mut().update(self, delta_time, hit_position);
-            self.current_scene = Some(scene);
+    fn to_design_position(&self, x: i32, y: i32) -> Option<(i32, i32)> {
+        let (viewport_x, viewport_y, viewport_width, viewport_height) = self.viewport;
+        let viewport_position = (x - viewport_x, y - viewport_y);
+        if viewport_position.0 < 0 || viewport_position.0 >= viewport_width ||
+            viewport_position.1 < 0 || viewport_position.1 >= viewport_height {
+            return None;
         }
+
+        let design_width = self.design_resolution.width as i32;
+        let design_height = self.design_resolution.height as i32;
+        Some((
+            viewport_position.0 * design_width / viewport_width,
+            design_height - viewport_position.1 * design_height / viewport_height
+        ))
+    }
+
+    pub fn get_design_resolution(&self) -> DesignResolution {
+        self.design_resolution
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn wait_for_next_frame(&self, frame_start_time: Instant) {
+        if let Some(target_frame_duration) = self.target_frame_duration {
+            let elapsed_time = frame_start_time.elapsed();
+            if elapsed_time < target_frame_duration {
+                std::thread::sleep(target_frame_duration - elapsed_time);
+            }
+        }
+    }
+
+    fn update(&mut self, input: &InputState) {
+        self.clock.advance_frame();
+        let current_time = self.clock.now();
+        let delta_time = ((current_time - self.last_updated_time) as f32).min(MAX_DELTA_TIME);
         self.last_updated_time = current_time;
+
+        match self.fixed_time_step {
+            Some(fixed_time_step) => {
+                self.accumulated_time += delta_time;
+                // 沒有執行到任何步進時保留輸入，留給下一幀
+                self.is_input_consumed = false;
+                while self.accumulated_time >= fixed_time_step {
+                    if self.is_input_consumed {
+                        self.update_scene(fixed_time_step, &input.without_actions());
+                    }
+                    else {
+                        self.update_scene(fixed_time_step, input);
+                        self.is_input_consumed = true;
+                    }
+                    self.accumulated_time -= fixed_time_step;
+                }
+                self.interpolation_alpha = self.accumulated_time / fixed_time_step;
+            },
+            None => {
+                self.update_scene(delta_time, input);
+                self.is_input_consumed = true;
+            }
+        }
+        self.apply_cursor();
+    }
+
+    fn update_scene(&mut self, delta_time: f32, input: &InputState) {
+        self.requested_cursor = SystemCursor::Arrow;
+        // 轉場中場景照常更新，但不接收輸入，避免玩家在畫面被蓋住時操作
+        let blocked_input;
+        let scene_input = if self.active_transition.is_some() {
+            self.update_transition(delta_time);
+            blocked_input = InputState::new(None, None, input.last_device, HashSet::new(), HashSet::new(), HashSet::new());
+            &blocked_input
+        } else {
+            input
+        };
+        if let Some(scene) = self.scene_stack.last().cloned() {
+            let mut scene = scene.borrow_mut();
+            scene.update(self, delta_time, scene_input);
+            if let Some(scene_graph) = scene.get_scene_graph_mut() {
+                scene_graph.update(delta_time);
+            }
+        }
+        self.process_scene_requests();
     }
 
     fn draw(&mut self) {
         unsafe {
             gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
         }
+        self.sprite_batch.reset_draw_call_count();
+
+        // 從最上層往下找到第一個非疊加場景，由下往上依序繪製
+        let first_visible_index = self.scene_stack
+            .iter()
+            .rposition(|scene| !scene.borrow().is_overlay())
+            .unwrap_or(0);
+        for scene in self.scene_stack.iter().skip(first_visible_index) {
+            unsafe {
+                gl::Clear(gl::DEPTH_BUFFER_BIT);
+            }
+            if let Some(scene_graph) = scene.borrow().get_scene_graph() {
+                scene_graph.draw(&mut self.sprite_batch);
+            }
+            self.sprite_batch.flush(self.current_projection_matrix);
+        }
+
+        self.draw_transition();
+    }
+
+    fn draw_transition(&mut self) {
+        let Some(active_transition) = &self.active_transition else {
+            return;
+        };
+
+        let cover_ratio = active_transition.transition.get_cover_ratio(active_transition.elapsed_time);
+        let (design_width, design_height) = self.design_resolution.get_size();
+        match active_transition.transition {
+            Transition::Fade(_) => {
+                self.transition_plane.set_rect((0.0, 0.0, design_width, design_height));
+                self.transition_plane.set_alpha(cover_ratio);
+            },
+            Transition::Wipe(_) => {
+                self.transition_plane.set_rect((0.0, 0.0, design_width * cover_ratio, design_height));
+                self.transition_plane.set_alpha(1.0);
+            },
+            Transition::None => return,
+        }
 
-        if let Some(scene) = self.current_scene.take() {
-            scene.borrow_mut().draw(self);
-            self.current_scene = Some(scene);
+        unsafe {
+            gl::Clear(gl::DEPTH_BUFFER_BIT);
         }
+        self.transition_plane.draw(&mut self.sprite_batch);
+        self.sprite_batch.flush(self.current_projection_matrix);
     }
 
+    // 直接替換整個場景堆疊，用於遊戲開始時載入第一個場景
     pub fn load_scene(&mut self, scene: Rc<RefCell<dyn Scene>>) {
-        self.current_scene = Some(scene);
+        self.scene_stack = vec![scene];
+    }
+
+    // 場景的切換都延後到這一幀的 update 結束後才處理
+    pub fn push_scene(&mut self, scene: Rc<RefCell<dyn Scene>>, transition: Transition) {
+        self.scene_requests.push_back((SceneRequest::Push(scene), transition));
+    }
+
+    pub fn pop_scene(&mut self, transition: Transition) {
+        self.scene_requests.push_back((SceneRequest::Pop, transition));
+    }
+
+    // 替換最上層的場景
+    pub fn change_scene(&mut self, scene: Rc<RefCell<dyn Scene>>, transition: Transition) {
+        self.scene_requests.push_back((SceneRequest::Change(scene), transition));
+    }
+
+    fn process_scene_requests(&mut self) {
+        while self.active_transition.is_none() {
+            let Some((scene_request, transition)) = self.scene_requests.pop_front() else {
+                break;
+            };
+
+            if transition.get_duration() <= 0.0 {
+                self.apply_scene_request(scene_request);
+            }
+            else {
+                self.active_transition = Some(ActiveTransition {
+                    transition,
+                    elapsed_time: 0.0,
+                    scene_request: Some(scene_request),
+                });
+            }
+        }
+    }
+
+    fn update_transition(&mut self, delta_time: f32) {
+        let Some(active_transition) = &mut self.active_transition else {
+            return;
+        };
+
+        active_transition.elapsed_time += delta_time;
+        let duration = active_transition.transition.get_duration();
+        // 畫面完全蓋住時才真正切換場景
+        let scene_request = if active_transition.elapsed_time >= duration / 2.0 {
+            active_transition.scene_request.take()
+        } else {
+            None
+        };
+        if active_transition.elapsed_time >= duration {
+            self.active_transition = None;
+        }
+
+        if let Some(scene_request) = scene_request {
+            self.apply_scene_request(scene_request);
+        }
+    }
+
+    fn apply_scene_request(&mut self, scene_request: SceneRequest) {
+        match scene_request {
+            SceneRequest::Push(scene) => self.scene_stack.push(scene),
+            SceneRequest::Pop => {
+                if self.scene_stack.len() > 1 {
+                    self.scene_stack.pop();
+                }
+                else {
+                    eprintln!("Can't pop the last scene");
+                }
+            },
+            SceneRequest::Change(scene) => {
+                self.scene_stack.pop();
+                self.scene_stack.push(scene);
+            },
+        }
+
+        if self.is_texture_logging {
+            self.drawable_generator.asset_manager.borrow().log_texture_usages();
+        }
+    }
+
+    // 場景每幀在 update 中呼叫，沒有要求時恢復成預設箭頭
+    pub fn request_cursor(&mut self, cursor: SystemCursor) {
+        self.requested_cursor = cursor;
+    }
+
+    fn apply_cursor(&mut self) {
+        let current_system_cursor = self.current_cursor.as_ref().map(|(system_cursor, _)| *system_cursor);
+        if current_system_cursor == Some(self.requested_cursor) {
+            return;
+        }
+
+        match Cursor::from_system(self.requested_cursor) {
+            Ok(cursor) => {
+                cursor.set();
+                self.current_cursor = Some((self.requested_cursor, cursor));
+            },
+            Err(error) => eprintln!("Can't create cursor: {}", error),
+        }
+    }
+
+    pub fn set_fullscreen(&mut self, is_fullscreen: bool) {
+        let fullscreen_type = if is_fullscreen { FullscreenType::Desktop } else { FullscreenType::Off };
+        if self.window.fullscreen_state() == fullscreen_type {
+            return;
+        }
+
+        if let Err(error) = self.window.set_fullscreen(fullscreen_type) {
+            eprintln!("Can't change fullscreen mode: {}", error);
+        }
     }
 
-    pub fn start_music(&mut self){
-        self.audio_manager.start_music();
+    pub fn quit(&mut self) {
+        self.is_quit_requested = true;
     }
 }
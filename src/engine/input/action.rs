@@ -0,0 +1,37 @@
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Action {
+    Advance,
+    Back,
+    Skip,
+    SkipToggle,
+    AutoToggle,
+    Menu,
+    HideUi,
+    Backlog,
+    Up,
+    Down,
+    Left,
+    Right,
+    Confirm,
+}
+
+impl Action {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Advance" => Some(Action::Advance),
+            "Back" => Some(Action::Back),
+            "Skip" => Some(Action::Skip),
+            "SkipToggle" => Some(Action::SkipToggle),
+            "AutoToggle" => Some(Action::AutoToggle),
+            "Menu" => Some(Action::Menu),
+            "HideUi" => Some(Action::HideUi),
+            "Backlog" => Some(Action::Backlog),
+            "Up" => Some(Action::Up),
+            "Down" => Some(Action::Down),
+            "Left" => Some(Action::Left),
+            "Right" => Some(Action::Right),
+            "Confirm" => Some(Action::Confirm),
+            _ => None,
+        }
+    }
+}
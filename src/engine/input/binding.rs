@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::fs;
+use sdl2::controller::Button;
+use sdl2::keyboard::Keycode;
+use sdl2::mouse::MouseButton;
+use super::action::Action;
+use super::touch_gesture::TouchGesture;
+
+const DEFAULT_BINDINGS: &str = include_str!("../../../resources/configs/input_bindings.cfg");
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum InputDevice {
+    Keyboard,
+    Mouse,
+    Controller,
+    Touch,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Binding {
+    Key(Keycode),
+    Mouse(MouseButton),
+    Controller(Button),
+    Touch(TouchGesture),
+}
+
+impl Binding {
+    pub fn get_device(&self) -> InputDevice {
+        match self {
+            Binding::Key(_) => InputDevice::Keyboard,
+            Binding::Mouse(_) => InputDevice::Mouse,
+            Binding::Controller(_) => InputDevice::Controller,
+            Binding::Touch(_) => InputDevice::Touch,
+        }
+    }
+
+    // 格式為 "裝置:名稱"，例如 "Key:Return"、"Mouse:Left"、"Controller:a"、"Touch:Tap"
+    pub fn from_name(name: &str) -> Option<Self> {
+        let (device, input_name) = name.split_once(':')?;
+        match device.trim() {
+            "Key" => Keycode::from_name(input_name.trim()).map(Binding::Key),
+            "Mouse" => match input_name.trim() {
+                "Left" => Some(Binding::Mouse(MouseButton::Left)),
+                "Middle" => Some(Binding::Mouse(MouseButton::Middle)),
+                "Right" => Some(Binding::Mouse(MouseButton::Right)),
+                _ => None,
+            },
+            "Controller" => Button::from_string(input_name.trim()).map(Binding::Controller),
+            "Touch" => match input_name.trim() {
+                "Tap" => Some(Binding::Touch(TouchGesture::Tap)),
+                "LongPress" => Some(Binding::Touch(TouchGesture::LongPress)),
+                "SwipeUp" => Some(Binding::Touch(TouchGesture::SwipeUp)),
+                "TwoFingerTap" => Some(Binding::Touch(TouchGesture::TwoFingerTap)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+pub struct InputBindings {
+    bindings: HashMap<Action, Vec<Binding>>,
+}
+
+impl InputBindings {
+    pub fn load(file_path: &str) -> Self {
+        match fs::read_to_string(file_path) {
+            Ok(content) => Self::parse(&content),
+            Err(error) => {
+                eprintln!("Can't read input bindings from {}: {}, using defaults", file_path, error);
+                Self::parse(DEFAULT_BINDINGS)
+            }
+        }
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let mut bindings: HashMap<Action, Vec<Binding>> = HashMap::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((action_name, binding_names)) = line.split_once('=') else {
+                eprintln!("Invalid input binding line: {}", line);
+                continue;
+            };
+            let Some(action) = Action::from_name(action_name.trim()) else {
+                eprintln!("Unknown input action: {}", action_name.trim());
+                continue;
+            };
+
+            let action_bindings = bindings.entry(action).or_default();
+            for binding_name in binding_names.split(',') {
+                match Binding::from_name(binding_name) {
+                    Some(binding) => action_bindings.push(binding),
+                    None => eprintln!("Unknown input binding: {}", binding_name.trim()),
+                }
+            }
+        }
+
+        Self { bindings }
+    }
+
+    pub fn get_actions(&self, binding: Binding) -> impl Iterator<Item = Action> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |(_, action_bindings)| action_bindings.contains(&binding))
+            .map(|(action, _)| *action)
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+use super::action::Action;
+use super::binding::{Binding, InputBindings, InputDevice};
+use super::input_state::InputState;
+
+pub struct InputManager {
+    bindings: InputBindings,
+    held_bindings: HashSet<Binding>,
+    pressed_bindings: HashSet<Binding>,
+    released_bindings: HashSet<Binding>,
+    hit_position: Option<(i32, i32)>,
+    pointer_position: Option<(i32, i32)>,
+    last_device: InputDevice,
+}
+
+impl InputManager {
+    pub fn new(bindings: InputBindings) -> Self {
+        Self {
+            bindings,
+            held_bindings: HashSet::new(),
+            pressed_bindings: HashSet::new(),
+            released_bindings: HashSet::new(),
+            hit_position: None,
+            pointer_position: None,
+            last_device: InputDevice::Mouse,
+        }
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.pressed_bindings.clear();
+        self.released_bindings.clear();
+        self.hit_position = None;
+    }
+
+    pub fn press(&mut self, binding: Binding) {
+        self.last_device = binding.get_device();
+        if self.held_bindings.insert(binding) {
+            self.pressed_bindings.insert(binding);
+        }
+    }
+
+    pub fn release(&mut self, binding: Binding) {
+        if self.held_bindings.remove(&binding) {
+            self.released_bindings.insert(binding);
+        }
+    }
+
+    pub fn set_hit_position(&mut self, hit_position: (i32, i32)) {
+        self.hit_position = Some(hit_position);
+    }
+
+    pub fn move_pointer(&mut self, pointer_position: (i32, i32)) {
+        self.pointer_position = Some(pointer_position);
+        self.last_device = InputDevice::Mouse;
+    }
+
+    // 觸控沒有懸停狀態，手指離開後就不該留下指標位置
+    pub fn clear_pointer(&mut self) {
+        self.pointer_position = None;
+    }
+
+    pub fn get_state(&self) -> InputState {
+        let held_actions = self.collect_actions(&self.held_bindings);
+        // 同一動作若還有其他綁定按著，就不算放開
+        let released_actions = self.collect_actions(&self.released_bindings)
+            .into_iter()
+            .filter(|action| !held_actions.contains(action))
+            .collect();
+        InputState::new(
+            self.hit_position,
+            self.pointer_position,
+            self.last_device,
+            self.collect_actions(&self.pressed_bindings),
+            held_actions,
+            released_actions)
+    }
+
+    fn collect_actions(&self, bindings: &HashSet<Binding>) -> HashSet<Action> {
+        bindings
+            .iter()
+            .flat_map(|binding| self.bindings.get_actions(*binding))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sdl2::keyboard::Keycode;
+    use super::*;
+
+    #[test]
+    fn press_hold_release_each_last_one_frame() {
+        let mut input_manager = InputManager::new(InputBindings::parse("Skip = Key:Left Ctrl"));
+        let binding = Binding::Key(Keycode::LCtrl);
+        let mut states = vec![];
+
+        input_manager.begin_frame();
+        input_manager.press(binding);
+        states.push(input_manager.get_state());
+        input_manager.begin_frame();
+        states.push(input_manager.get_state());
+        input_manager.begin_frame();
+        input_manager.release(binding);
+        states.push(input_manager.get_state());
+        input_manager.begin_frame();
+        states.push(input_manager.get_state());
+
+        let flags = states
+            .iter()
+            .map(|state| (state.is_pressed(Action::Skip), state.is_held(Action::Skip), state.is_released(Action::Skip)))
+            .collect::<Vec<_>>();
+        assert_eq!(flags, vec![
+            (true, true, false),
+            (false, true, false),
+            (false, false, true),
+            (false, false, false),
+        ]);
+    }
+}
@@ -0,0 +1,54 @@
+use std::collections::HashSet;
+use super::action::Action;
+use super::binding::InputDevice;
+
+pub struct InputState {
+    pub hit_position: Option<(i32, i32)>,
+    pub pointer_position: Option<(i32, i32)>,
+    pub last_device: InputDevice,
+    pressed_actions: HashSet<Action>,
+    held_actions: HashSet<Action>,
+    released_actions: HashSet<Action>,
+}
+
+impl InputState {
+    pub fn new(
+        hit_position: Option<(i32, i32)>,
+        pointer_position: Option<(i32, i32)>,
+        last_device: InputDevice,
+        pressed_actions: HashSet<Action>,
+        held_actions: HashSet<Action>,
+        released_actions: HashSet<Action>) -> Self {
+        Self {
+            hit_position,
+            pointer_position,
+            last_device,
+            pressed_actions,
+            held_actions,
+            released_actions,
+        }
+    }
+
+    // 去掉這一幀的按下與放開，保留游標位置、裝置與按住中的動作
+    pub fn without_actions(&self) -> Self {
+        Self::new(
+            None,
+            self.pointer_position,
+            self.last_device,
+            HashSet::new(),
+            self.held_actions.clone(),
+            HashSet::new())
+    }
+
+    pub fn is_pressed(&self, action: Action) -> bool {
+        self.pressed_actions.contains(&action)
+    }
+
+    pub fn is_held(&self, action: Action) -> bool {
+        self.held_actions.contains(&action)
+    }
+
+    pub fn is_released(&self, action: Action) -> bool {
+        self.released_actions.contains(&action)
+    }
+}
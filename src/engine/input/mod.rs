@@ -0,0 +1,5 @@
+pub(crate) mod action;
+pub(crate) mod binding;
+pub(crate) mod input_state;
+pub(crate) mod input_manager;
+pub(crate) mod touch_gesture;
\ No newline at end of file
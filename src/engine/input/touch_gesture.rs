@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+const TAP_MAX_MILLISECONDS: u32 = 300;
+const LONG_PRESS_MILLISECONDS: u32 = 500;
+const MOVE_TOLERANCE: f32 = 0.02;
+const SWIPE_MIN_DISTANCE: f32 = 0.15;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TouchGesture {
+    Tap,
+    LongPress,
+    SwipeUp,
+    TwoFingerTap,
+}
+
+pub enum TouchGestureEvent {
+    Pressed(TouchGesture, (f32, f32)),
+    Released(TouchGesture),
+}
+
+struct TouchPoint {
+    start_position: (f32, f32),
+    current_position: (f32, f32),
+}
+
+// 座標使用 SDL 的正規化觸控座標 (0.0 ~ 1.0，左上為原點)
+pub struct TouchGestureRecognizer {
+    touch_points: HashMap<i64, TouchPoint>,
+    start_timestamp: u32,
+    max_touch_count: usize,
+    is_long_pressed: bool,
+    is_moved: bool,
+}
+
+impl TouchGestureRecognizer {
+    pub fn new() -> Self {
+        Self {
+            touch_points: HashMap::new(),
+            start_timestamp: 0,
+            max_touch_count: 0,
+            is_long_pressed: false,
+            is_moved: false,
+        }
+    }
+
+    pub fn finger_down(&mut self, finger_id: i64, position: (f32, f32), timestamp: u32) {
+        if self.touch_points.is_empty() {
+            self.start_timestamp = timestamp;
+            self.max_touch_count = 0;
+            self.is_long_pressed = false;
+            self.is_moved = false;
+        }
+
+        self.touch_points.insert(finger_id, TouchPoint {
+            start_position: position,
+            current_position: position,
+        });
+        self.max_touch_count = self.max_touch_count.max(self.touch_points.len());
+    }
+
+    pub fn finger_motion(&mut self, finger_id: i64, position: (f32, f32)) {
+        if let Some(touch_point) = self.touch_points.get_mut(&finger_id) {
+            touch_point.current_position = position;
+            let distance = get_distance(touch_point.start_position, position);
+            if distance > MOVE_TOLERANCE {
+                self.is_moved = true;
+            }
+        }
+    }
+
+    pub fn finger_up(&mut self, finger_id: i64, position: (f32, f32), timestamp: u32) -> Option<TouchGestureEvent> {
+        let touch_point = self.touch_points.remove(&finger_id)?;
+        if !self.touch_points.is_empty() {
+            return None;
+        }
+
+        if self.is_long_pressed {
+            return Some(TouchGestureEvent::Released(TouchGesture::LongPress));
+        }
+
+        let duration = timestamp.saturating_sub(self.start_timestamp);
+        if self.max_touch_count >= 2 {
+            return if duration <= TAP_MAX_MILLISECONDS {
+                Some(TouchGestureEvent::Pressed(TouchGesture::TwoFingerTap, position))
+            } else {
+                None
+            };
+        }
+
+        let delta_x = position.0 - touch_point.start_position.0;
+        let delta_y = touch_point.start_position.1 - position.1;
+        if delta_y >= SWIPE_MIN_DISTANCE && delta_y.abs() > delta_x.abs() {
+            return Some(TouchGestureEvent::Pressed(TouchGesture::SwipeUp, position));
+        }
+
+        if !self.is_moved && get_distance(touch_point.start_position, position) <= MOVE_TOLERANCE {
+            return Some(TouchGestureEvent::Pressed(TouchGesture::Tap, position));
+        }
+
+        None
+    }
+
+    // 長按需要在手指還沒放開時觸發，所以每幀都要檢查
+    pub fn update(&mut self, timestamp: u32) -> Option<TouchGestureEvent> {
+        if self.touch_points.len() != 1 || self.max_touch_count != 1 || self.is_long_pressed || self.is_moved {
+            return None;
+        }
+
+        if timestamp.saturating_sub(self.start_timestamp) < LONG_PRESS_MILLISECONDS {
+            return None;
+        }
+
+        self.is_long_pressed = true;
+        let position = self.touch_points.values().next()?.current_position;
+        Some(TouchGestureEvent::Pressed(TouchGesture::LongPress, position))
+    }
+}
+
+fn get_distance(from: (f32, f32), to: (f32, f32)) -> f32 {
+    ((to.0 - from.0).powi(2) + (to.1 - from.1).powi(2)).sqrt()
+}
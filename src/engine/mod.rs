@@ -1,7 +1,17 @@
 pub mod game;
+pub mod design_resolution;
+pub mod engine_error;
+pub mod clock;
 pub(crate) mod scene;
+pub mod scene_graph;
+pub mod transition;
+pub mod transform;
+pub mod tween;
+pub mod timeline;
 pub mod component;
 pub mod drawable_component;
-mod rendering;
+pub(crate) mod input;
+pub(crate) mod rendering;
 pub(crate) mod drawable_implements;
-mod audio_manager;
\ No newline at end of file
+mod audio_manager;
+pub(crate) mod asset_manager;
\ No newline at end of file
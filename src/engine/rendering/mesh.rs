@@ -1,5 +0,0 @@
-pub struct Mesh {
-    pub vertices: Vec<f32>,
-    pub tex_coords: Vec<f32>,
-    pub indices: Vec<u32>,
-}
\ No newline at end of file
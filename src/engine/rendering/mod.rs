@@ -1,3 +1,4 @@
-pub(crate) mod mesh;
 pub(crate) mod material;
-pub(crate) mod drawable_object;
+pub(crate) mod texture;
+pub(crate) mod sprite_batch;
+pub(crate) mod shader_registry;
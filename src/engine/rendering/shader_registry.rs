@@ -0,0 +1,143 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::rc::Rc;
+use crate::engine::engine_error::EngineError;
+
+// 連結好的 shader program，uniform 位置查過一次就記下來
+pub struct ShaderProgram {
+    pub id: u32,
+    uniform_locations: RefCell<HashMap<String, i32>>,
+}
+
+impl ShaderProgram {
+    pub(crate) fn new(id: u32) -> Self {
+        Self {
+            id,
+            uniform_locations: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn get_uniform_location(&self, name: &str) -> i32 {
+        if let Some(location) = self.uniform_locations.borrow().get(name) {
+            return *location;
+        }
+
+        let location = CString::new(name)
+            .map_or(-1, |c_name| unsafe { gl::GetUniformLocation(self.id, c_name.as_ptr()) });
+        self.uniform_locations.borrow_mut().insert(name.to_string(), location);
+        location
+    }
+}
+
+impl Drop for ShaderProgram {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteProgram(self.id);
+        }
+    }
+}
+
+// 同一組 vertex 與 fragment shader 只編譯一次，材質之間共用同一個 program
+pub struct ShaderRegistry {
+    shader_programs: HashMap<(String, String), Rc<ShaderProgram>>,
+    named_shader_programs: HashMap<String, Rc<ShaderProgram>>,
+}
+
+impl ShaderRegistry {
+    pub fn new() -> Self {
+        Self {
+            shader_programs: HashMap::new(),
+            named_shader_programs: HashMap::new(),
+        }
+    }
+
+    pub fn get_or_compile(&mut self, vertex_shader: &str, fragment_shader: &str) -> Result<Rc<ShaderProgram>, EngineError> {
+        let key = (vertex_shader.to_string(), fragment_shader.to_string());
+        if let Some(shader_program) = self.shader_programs.get(&key) {
+            return Ok(Rc::clone(shader_program));
+        }
+
+        let shader_program = Rc::new(ShaderProgram::new(create_shader_program(vertex_shader, fragment_shader)?));
+        self.shader_programs.insert(key, Rc::clone(&shader_program));
+        Ok(shader_program)
+    }
+
+    // 自訂 shader 以名稱註冊，之後用名稱取得
+    pub fn register(&mut self, name: &str, vertex_shader: &str, fragment_shader: &str) -> Result<(), EngineError> {
+        let shader_program = self.get_or_compile(vertex_shader, fragment_shader)?;
+        self.named_shader_programs.insert(name.to_string(), shader_program);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Result<Rc<ShaderProgram>, EngineError> {
+        self.named_shader_programs
+            .get(name)
+            .cloned()
+            .ok_or_else(|| EngineError::Gl(format!("Shader \"{}\" isn't registered", name)))
+    }
+}
+
+fn create_shader_program(vertex_shader_src: &str, fragment_shader_src: &str) -> Result<u32, EngineError> {
+    let vertex_shader = compile_shader(vertex_shader_src, gl::VERTEX_SHADER)?;
+    let fragment_shader = match compile_shader(fragment_shader_src, gl::FRAGMENT_SHADER) {
+        Ok(fragment_shader) => fragment_shader,
+        Err(error) => {
+            unsafe { gl::DeleteShader(vertex_shader) };
+            return Err(error);
+        }
+    };
+
+    unsafe {
+        let shader_program = gl::CreateProgram();
+        gl::AttachShader(shader_program, vertex_shader);
+        gl::AttachShader(shader_program, fragment_shader);
+        gl::LinkProgram(shader_program);
+        // 連結後 shader 已經不需要
+        gl::DeleteShader(vertex_shader);
+        gl::DeleteShader(fragment_shader);
+
+        // 檢查連接錯誤
+        let mut success: gl::types::GLint = 1;
+        gl::GetProgramiv(shader_program, gl::LINK_STATUS, &mut success);
+        if success == 0 {
+            let log = read_info_log(|length, log| gl::GetProgramInfoLog(shader_program, length, std::ptr::null_mut(), log));
+            gl::DeleteProgram(shader_program);
+            return Err(EngineError::ShaderLink { log });
+        }
+
+        Ok(shader_program)
+    }
+}
+
+fn compile_shader(source: &str, shader_type: u32) -> Result<u32, EngineError> {
+    let c_str = std::ffi::CString::new(source.as_bytes())
+        .map_err(|_| EngineError::ShaderCompile { log: "Shader source contains a nul byte".to_string() })?;
+    unsafe {
+        let shader = gl::CreateShader(shader_type);
+        gl::ShaderSource(shader, 1, &c_str.as_ptr(), std::ptr::null());
+        gl::CompileShader(shader);
+
+        // 檢查編譯錯誤
+        let mut success: gl::types::GLint = 1;
+        gl::GetShaderiv(shader, gl::COMPILE_STATUS, &mut success);
+        if success == 0 {
+            let log = read_info_log(|length, log| gl::GetShaderInfoLog(shader, length, std::ptr::null_mut(), log));
+            gl::DeleteShader(shader);
+            return Err(EngineError::ShaderCompile { log });
+        }
+
+        Ok(shader)
+    }
+}
+
+fn read_info_log(get_info_log: impl FnOnce(gl::types::GLsizei, *mut gl::types::GLchar)) -> String {
+    let mut log: [gl::types::GLchar; 512] = [0; 512];
+    get_info_log(log.len() as gl::types::GLsizei, log.as_mut_ptr());
+
+    // 將 &[i8] 轉換為 &[u8]
+    let log_u8 = unsafe {
+        std::slice::from_raw_parts(log.as_ptr() as *const u8, log.len())
+    };
+    String::from_utf8_lossy(log_u8).trim_end_matches('\0').to_string()
+}
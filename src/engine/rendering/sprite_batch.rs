@@ -0,0 +1,232 @@
+use std::rc::Rc;
+use nalgebra_glm::{Mat4, Vec4};
+use super::material::Material;
+use super::shader_registry::ShaderProgram;
+
+pub const VERTICES_PER_QUAD: usize = 4;
+const INDICES_PER_QUAD: usize = 6;
+const INITIAL_QUAD_CAPACITY: usize = 1024;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct SpriteVertex {
+    position: [f32; 3],
+    color: [f32; 4],
+    tex_coord: [f32; 2],
+    tint: [f32; 4],
+}
+
+// rect 與 tex_rect 都是 (x, y, 寬, 高)，tex_rect 的 y 以圖片上緣為 0
+// vertex_colors 依左下、右下、右上、左上的順序，可用來做漸層；tint 整個四邊形共用，淡入淡出只需要改它
+pub struct QuadDescriptor<'a> {
+    pub material: &'a Material,
+    pub model_matrix: &'a Mat4,
+    pub rect: (f32, f32, f32, f32),
+    pub z_index: f32,
+    pub tex_rect: (f32, f32, f32, f32),
+    pub vertex_colors: &'a [(f32, f32, f32, f32); VERTICES_PER_QUAD],
+    pub tint: (f32, f32, f32, f32),
+}
+
+struct SpriteQuad {
+    shader_program: Rc<ShaderProgram>,
+    texture_id: u32,
+    z_index: f32,
+    vertices: [SpriteVertex; VERTICES_PER_QUAD],
+}
+
+// 每幀收集所有四邊形，依深度與材質排序後寫進同一個頂點緩衝區，材質相同的連續四邊形合併成一次繪製
+// shader 只有 uProjection 一個矩陣 uniform：每個物件的 model matrix 若設成 uniform，就得每個物件各畫一次，
+// 所以改在 CPU 上套用到頂點，不同物件才能合併成同一次繪製
+pub struct SpriteBatch {
+    vao: u32,
+    vbo: u32,
+    ebo: u32,
+    quad_capacity: usize,
+    quads: Vec<SpriteQuad>,
+    vertices: Vec<SpriteVertex>,
+    draw_call_count: usize,
+}
+
+impl SpriteBatch {
+    pub fn new() -> Self {
+        let mut vao: u32 = 0;
+        let mut vbo: u32 = 0;
+        let mut ebo: u32 = 0;
+
+        unsafe {
+            gl::GenVertexArrays(1, &mut vao);
+            gl::BindVertexArray(vao);
+
+            // 頂點緩衝區 (VBO)，位置、顏色、貼圖座標與色調交錯存放
+            gl::GenBuffers(1, &mut vbo);
+            gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+            let stride = std::mem::size_of::<SpriteVertex>() as gl::types::GLsizei;
+            gl::VertexAttribPointer(0, 3, gl::FLOAT, gl::FALSE, stride, std::ptr::null());
+            gl::EnableVertexAttribArray(0);
+            gl::VertexAttribPointer(1, 4, gl::FLOAT, gl::FALSE, stride, (3 * std::mem::size_of::<f32>()) as *const gl::types::GLvoid);
+            gl::EnableVertexAttribArray(1);
+            gl::VertexAttribPointer(2, 2, gl::FLOAT, gl::FALSE, stride, (7 * std::mem::size_of::<f32>()) as *const gl::types::GLvoid);
+            gl::EnableVertexAttribArray(2);
+            gl::VertexAttribPointer(3, 4, gl::FLOAT, gl::FALSE, stride, (9 * std::mem::size_of::<f32>()) as *const gl::types::GLvoid);
+            gl::EnableVertexAttribArray(3);
+
+            // 索引緩衝區 (EBO)
+            gl::GenBuffers(1, &mut ebo);
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, ebo);
+
+            gl::BindVertexArray(0);
+        }
+
+        let mut sprite_batch = Self {
+            vao,
+            vbo,
+            ebo,
+            quad_capacity: 0,
+            quads: vec![],
+            vertices: vec![],
+            draw_call_count: 0,
+        };
+        sprite_batch.reserve_indices(INITIAL_QUAD_CAPACITY);
+        sprite_batch
+    }
+
+    pub fn add_quad(&mut self, quad: &QuadDescriptor) {
+        let QuadDescriptor { material, model_matrix, rect, z_index, tex_rect, vertex_colors, tint } = *quad;
+        let (x, y, width, height) = rect;
+        let (u, v, tex_width, tex_height) = tex_rect;
+        let tint = [tint.0, tint.1, tint.2, tint.3];
+        let vertex = |position: (f32, f32), color: (f32, f32, f32, f32), tex_coord: (f32, f32)| SpriteVertex {
+            position: {
+                // model matrix 在這裡套用，不設成 uModel uniform，原因見 SpriteBatch 的說明
+                let position = model_matrix * Vec4::new(position.0, position.1, z_index, 1.0);
+                [position.x, position.y, position.z]
+            },
+            color: [color.0, color.1, color.2, color.3],
+            tex_coord: [tex_coord.0, tex_coord.1],
+            tint,
+        };
+        let vertices = [
+            vertex((x, y), vertex_colors[0], (u, v + tex_height)),
+            vertex((x + width, y), vertex_colors[1], (u + tex_width, v + tex_height)),
+            vertex((x + width, y + height), vertex_colors[2], (u + tex_width, v)),
+            vertex((x, y + height), vertex_colors[3], (u, v)),
+        ];
+        self.quads.push(SpriteQuad {
+            shader_program: Rc::clone(&material.shader_program),
+            texture_id: material.texture.as_ref().map_or(0, |texture| texture.id),
+            // 以套用矩陣後的 z 排序，場景節點的 z_order 才會影響繪製順序；平面上的變換不會改變 z，四個頂點相同
+            z_index: vertices[0].position[2],
+            vertices,
+        });
+    }
+
+    pub fn get_draw_call_count(&self) -> usize {
+        self.draw_call_count
+    }
+
+    pub fn reset_draw_call_count(&mut self) {
+        self.draw_call_count = 0;
+    }
+
+    pub fn flush(&mut self, projection_matrix: Mat4) {
+        if self.quads.is_empty() {
+            return;
+        }
+
+        // 由後往前畫，半透明的部分才能和後面的內容混合；同一層再依 shader 與貼圖排在一起
+        self.quads.sort_by(|a, b| {
+            b.z_index.total_cmp(&a.z_index)
+                .then(a.shader_program.id.cmp(&b.shader_program.id))
+                .then(a.texture_id.cmp(&b.texture_id))
+        });
+        if self.quads.len() > self.quad_capacity {
+            self.reserve_indices(self.quads.len().next_power_of_two());
+        }
+
+        self.vertices.clear();
+        self.vertices.extend(self.quads.iter().flat_map(|quad| quad.vertices));
+
+        unsafe {
+            gl::BindVertexArray(self.vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                (self.vertices.len() * std::mem::size_of::<SpriteVertex>()) as gl::types::GLsizeiptr,
+                self.vertices.as_ptr() as *const gl::types::GLvoid,
+                gl::STREAM_DRAW,
+            );
+            gl::ActiveTexture(gl::TEXTURE0);
+            // 已經由後往前排好，不寫入深度，避免透明的像素擋住之後同一深度的四邊形（例如文字重疊的字形）
+            gl::DepthMask(gl::FALSE);
+
+            let mut current_shader_program_id = None;
+            let mut start_index = 0;
+            while start_index < self.quads.len() {
+                let shader_program = &self.quads[start_index].shader_program;
+                let texture_id = self.quads[start_index].texture_id;
+                let end_index = self.quads[start_index..]
+                    .iter()
+                    .position(|quad| quad.shader_program.id != shader_program.id || quad.texture_id != texture_id)
+                    .map_or(self.quads.len(), |offset| start_index + offset);
+
+                if current_shader_program_id != Some(shader_program.id) {
+                    gl::UseProgram(shader_program.id);
+                    gl::UniformMatrix4fv(
+                        shader_program.get_uniform_location("uProjection"),
+                        1,
+                        gl::FALSE,
+                        projection_matrix.as_ptr());
+                    current_shader_program_id = Some(shader_program.id);
+                }
+                gl::BindTexture(gl::TEXTURE_2D, texture_id);
+                gl::DrawElements(
+                    gl::TRIANGLES,
+                    ((end_index - start_index) * INDICES_PER_QUAD) as i32,
+                    gl::UNSIGNED_INT,
+                    (start_index * INDICES_PER_QUAD * std::mem::size_of::<u32>()) as *const gl::types::GLvoid);
+                self.draw_call_count += 1;
+
+                start_index = end_index;
+            }
+
+            gl::DepthMask(gl::TRUE);
+            gl::BindVertexArray(0);
+        }
+
+        self.quads.clear();
+    }
+
+    // 所有四邊形的索引規律相同，預先產生到足夠的數量
+    fn reserve_indices(&mut self, quad_capacity: usize) {
+        let indices = (0..quad_capacity as u32)
+            .flat_map(|quad_index| {
+                let base_index = quad_index * VERTICES_PER_QUAD as u32;
+                [base_index, base_index + 1, base_index + 2, base_index + 2, base_index + 3, base_index]
+            })
+            .collect::<Vec<u32>>();
+
+        unsafe {
+            gl::BindVertexArray(self.vao);
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.ebo);
+            gl::BufferData(
+                gl::ELEMENT_ARRAY_BUFFER,
+                (indices.len() * std::mem::size_of::<u32>()) as gl::types::GLsizeiptr,
+                indices.as_ptr() as *const gl::types::GLvoid,
+                gl::STATIC_DRAW,
+            );
+            gl::BindVertexArray(0);
+        }
+        self.quad_capacity = quad_capacity;
+    }
+}
+
+impl Drop for SpriteBatch {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteVertexArrays(1, &self.vao);
+            gl::DeleteBuffers(1, &self.vbo);
+            gl::DeleteBuffers(1, &self.ebo);
+        }
+    }
+}
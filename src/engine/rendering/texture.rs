@@ -0,0 +1,114 @@
+use sdl2::surface::Surface;
+use crate::engine::engine_error::EngineError;
+
+// 洋紅與黑色的棋盤格，素材讀取失敗時用來代替，一眼就能看出缺了什麼
+const PLACEHOLDER_PIXELS: [u8; 16] = [
+    255, 0, 255, 255,
+    0, 0, 0, 255,
+    0, 0, 0, 255,
+    255, 0, 255, 255,
+];
+
+// 擁有 GL 貼圖，最後一個參照釋放時一併刪除
+pub struct Texture {
+    pub id: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Texture {
+    pub fn get_byte_size(&self) -> usize {
+        self.width as usize * self.height as usize * 4
+    }
+}
+
+impl Drop for Texture {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteTextures(1, &self.id);
+        }
+    }
+}
+
+pub fn create_texture_from_surface(surface: Surface) -> Result<Texture, EngineError> {
+    let surface = surface
+        .convert_format(sdl2::pixels::PixelFormatEnum::RGBA32)
+        .map_err(EngineError::Sdl)?;
+    let width = surface.width();
+    let height = surface.height();
+    let surface_pixels = surface
+        .without_lock()
+        .ok_or_else(|| EngineError::Sdl("Surface must be locked".to_string()))?;
+    Ok(create_texture(width, height, surface_pixels))
+}
+
+pub fn create_empty_texture(width: u32, height: u32) -> Texture {
+    create_texture(width, height, &vec![0; width as usize * height as usize * 4])
+}
+
+pub fn update_texture_from_surface(texture: &Texture, position: (u32, u32), surface: Surface) -> Result<(), EngineError> {
+    let surface = surface
+        .convert_format(sdl2::pixels::PixelFormatEnum::RGBA32)
+        .map_err(EngineError::Sdl)?;
+    let width = surface.width() as usize;
+    let height = surface.height() as usize;
+    let pitch = surface.pitch() as usize;
+    let surface_pixels = surface
+        .without_lock()
+        .ok_or_else(|| EngineError::Sdl("Surface must be locked".to_string()))?;
+    // 去掉每列結尾補齊用的位元組
+    let pixels = (0..height)
+        .flat_map(|row| &surface_pixels[row * pitch..row * pitch + width * 4])
+        .copied()
+        .collect::<Vec<u8>>();
+
+    unsafe {
+        gl::BindTexture(gl::TEXTURE_2D, texture.id);
+        gl::TexSubImage2D(
+            gl::TEXTURE_2D,
+            0,
+            position.0 as i32,
+            position.1 as i32,
+            width as i32,
+            height as i32,
+            gl::RGBA,
+            gl::UNSIGNED_BYTE,
+            pixels.as_ptr() as *const std::os::raw::c_void,
+        );
+    }
+    Ok(())
+}
+
+pub fn create_placeholder_texture() -> Texture {
+    create_texture(2, 2, &PLACEHOLDER_PIXELS)
+}
+
+fn create_texture(width: u32, height: u32, pixels: &[u8]) -> Texture {
+    let mut texture_id: u32 = 0;
+    unsafe {
+        gl::GenTextures(1, &mut texture_id);
+        gl::BindTexture(gl::TEXTURE_2D, texture_id);
+        gl::TexImage2D(
+            gl::TEXTURE_2D,
+            0,
+            gl::RGBA as i32,
+            width as i32,
+            height as i32,
+            0,
+            gl::RGBA,
+            gl::UNSIGNED_BYTE,
+            pixels.as_ptr() as *const std::os::raw::c_void,
+        );
+
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+    }
+
+    Texture {
+        id: texture_id,
+        width,
+        height,
+    }
+}
@@ -1,6 +1,21 @@
 use crate::engine::game::Game;
+use crate::engine::input::input_state::InputState;
+use crate::engine::scene_graph::SceneGraph;
 
 pub trait Scene {
-    fn update(&mut self, game: &mut Game, delta_time: f32, hit_position: Option<(i32, i32)>);
-    fn draw(&self, game: &mut Game);
+    fn update(&mut self, game: &mut Game, delta_time: f32, input: &InputState);
+
+    // 場景只負責建立與修改場景圖，元件的更新與繪製由引擎負責
+    fn get_scene_graph(&self) -> Option<&SceneGraph> {
+        None
+    }
+
+    fn get_scene_graph_mut(&mut self) -> Option<&mut SceneGraph> {
+        None
+    }
+
+    // 疊加在其他場景上的場景（選單、回顧等），下層場景仍會繪製但不會更新
+    fn is_overlay(&self) -> bool {
+        false
+    }
 }
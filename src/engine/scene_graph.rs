@@ -0,0 +1,226 @@
+use nalgebra_glm::{Mat4, Vec3};
+use crate::engine::component::Component;
+use crate::engine::rendering::sprite_batch::SpriteBatch;
+use crate::engine::transform::Transform;
+
+// generation 在節點移除時增加，空位被重複使用後舊的 NodeId 就不再有效
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NodeId {
+    index: usize,
+    generation: u32,
+}
+
+pub struct SceneNode {
+    pub transform: Transform,
+    pub is_visible: bool,
+    // 加到子節點所有物件的 z 上，越小越前面
+    pub z_order: f32,
+    component: Option<Box<dyn Component>>,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+impl SceneNode {
+    fn new(parent: Option<NodeId>, component: Option<Box<dyn Component>>) -> Self {
+        Self {
+            transform: Transform::new(),
+            is_visible: true,
+            z_order: 0.0,
+            component,
+            parent,
+            children: vec![],
+        }
+    }
+
+    fn get_local_matrix(&self) -> Mat4 {
+        nalgebra_glm::translation(&Vec3::new(0.0, 0.0, self.z_order)) *
+            self.transform.get_model_matrix((0.0, 0.0, 0.0, 0.0))
+    }
+}
+
+struct NodeSlot {
+    generation: u32,
+    node: Option<SceneNode>,
+}
+
+// 節點存在陣列裡，以索引互相參照；移除的節點留下空位給之後加入的節點使用
+pub struct SceneGraph {
+    slots: Vec<NodeSlot>,
+    free_indices: Vec<usize>,
+    root: NodeId,
+}
+
+impl SceneGraph {
+    pub fn new() -> Self {
+        Self {
+            slots: vec![NodeSlot { generation: 0, node: Some(SceneNode::new(None, None)) }],
+            free_indices: vec![],
+            root: NodeId { index: 0, generation: 0 },
+        }
+    }
+
+    pub fn get_root(&self) -> NodeId {
+        self.root
+    }
+
+    pub fn add_node(&mut self, parent: NodeId, component: Option<Box<dyn Component>>) -> NodeId {
+        let node = Some(SceneNode::new(Some(parent), component));
+        let node_id = match self.free_indices.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index];
+                slot.node = node;
+                NodeId { index, generation: slot.generation }
+            },
+            None => {
+                self.slots.push(NodeSlot { generation: 0, node });
+                NodeId { index: self.slots.len() - 1, generation: 0 }
+            },
+        };
+        if let Some(parent_node) = self.get_node_mut(parent) {
+            parent_node.children.push(node_id);
+        }
+        node_id
+    }
+
+    pub fn remove_node(&mut self, node_id: NodeId) {
+        if node_id == self.root {
+            return;
+        }
+        let Some(slot) = self.slots.get_mut(node_id.index).filter(|slot| slot.generation == node_id.generation) else {
+            return;
+        };
+        let Some(node) = slot.node.take() else {
+            return;
+        };
+        slot.generation += 1;
+        self.free_indices.push(node_id.index);
+        if let Some(parent_node) = node.parent.and_then(|parent| self.get_node_mut(parent)) {
+            parent_node.children.retain(|child| *child != node_id);
+        }
+        for child in node.children {
+            self.remove_node(child);
+        }
+    }
+
+    pub fn clear_children(&mut self, node_id: NodeId) {
+        for child in self.get_children(node_id) {
+            self.remove_node(child);
+        }
+    }
+
+    pub fn get_node(&self, node_id: NodeId) -> Option<&SceneNode> {
+        self.slots
+            .get(node_id.index)
+            .filter(|slot| slot.generation == node_id.generation)
+            .and_then(|slot| slot.node.as_ref())
+    }
+
+    pub fn get_node_mut(&mut self, node_id: NodeId) -> Option<&mut SceneNode> {
+        self.slots
+            .get_mut(node_id.index)
+            .filter(|slot| slot.generation == node_id.generation)
+            .and_then(|slot| slot.node.as_mut())
+    }
+
+    pub fn get_children(&self, node_id: NodeId) -> Vec<NodeId> {
+        self.get_node(node_id).map_or(vec![], |node| node.children.clone())
+    }
+
+    pub fn set_visible(&mut self, node_id: NodeId, is_visible: bool) {
+        if let Some(node) = self.get_node_mut(node_id) {
+            node.is_visible = is_visible;
+        }
+    }
+
+    pub fn set_component(&mut self, node_id: NodeId, component: Option<Box<dyn Component>>) {
+        if let Some(node) = self.get_node_mut(node_id) {
+            node.component = component;
+        }
+    }
+
+    pub fn has_component(&self, node_id: NodeId) -> bool {
+        self.get_node(node_id).is_some_and(|node| node.component.is_some())
+    }
+
+    pub fn get_component<T: 'static>(&self, node_id: NodeId) -> Option<&T> {
+        self.get_node(node_id)?.component.as_ref()?.as_any().downcast_ref::<T>()
+    }
+
+    pub fn get_component_mut<T: 'static>(&mut self, node_id: NodeId) -> Option<&mut T> {
+        self.get_node_mut(node_id)?.component.as_mut()?.as_any_mut().downcast_mut::<T>()
+    }
+
+    // 依加入的順序列出子節點上指定型別的元件
+    pub fn get_child_components_mut<T: 'static>(&mut self, parent: NodeId) -> Vec<&mut T> {
+        // 子節點的 generation 一定是目前的，依索引由小到大切開陣列，才能同時取得多個可變參照
+        let mut children = self.get_children(parent).into_iter().enumerate().collect::<Vec<_>>();
+        children.sort_by_key(|(_, child)| child.index);
+        let mut remaining_slots = &mut self.slots[..];
+        let mut offset = 0;
+        let mut child_components = vec![];
+        for (order, child) in children {
+            let Some((slot, rest)) = std::mem::take(&mut remaining_slots)[child.index - offset..].split_first_mut() else {
+                break;
+            };
+            remaining_slots = rest;
+            offset = child.index + 1;
+            if let Some(component) = slot.node.as_mut()
+                .and_then(|node| node.component.as_mut())
+                .and_then(|component| component.as_any_mut().downcast_mut::<T>()) {
+                child_components.push((order, component));
+            }
+        }
+        child_components.sort_by_key(|(order, _)| *order);
+        child_components.into_iter().map(|(_, component)| component).collect()
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        for node in self.slots.iter_mut().filter_map(|slot| slot.node.as_mut()) {
+            if let Some(component) = &mut node.component {
+                component.update(delta_time);
+            }
+        }
+    }
+
+    pub fn draw(&self, sprite_batch: &mut SpriteBatch) {
+        self.draw_node(self.root, &Mat4::identity(), sprite_batch);
+    }
+
+    // 隱藏的節點連同子節點都不繪製
+    fn draw_node(&self, node_id: NodeId, parent_matrix: &Mat4, sprite_batch: &mut SpriteBatch) {
+        let Some(node) = self.get_node(node_id).filter(|node| node.is_visible) else {
+            return;
+        };
+
+        let world_matrix = parent_matrix * node.get_local_matrix();
+        if let Some(drawable) = node.component.as_ref().and_then(|component| component.as_drawable()) {
+            drawable.draw_with_transform(sprite_batch, &world_matrix);
+        }
+        for child in &node.children {
+            self.draw_node(*child, &world_matrix, sprite_batch);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removed_slot_is_reused_and_stale_id_is_rejected() {
+        let mut scene_graph = SceneGraph::new();
+        let root = scene_graph.get_root();
+        let removed_node = scene_graph.add_node(root, None);
+        scene_graph.remove_node(removed_node);
+
+        let reused_node = scene_graph.add_node(root, None);
+        assert_eq!(reused_node.index, removed_node.index);
+        assert!(scene_graph.get_node(removed_node).is_none());
+        assert!(scene_graph.get_node(reused_node).is_some());
+
+        // 舊的 NodeId 不會移除到新的節點
+        scene_graph.remove_node(removed_node);
+        assert!(scene_graph.get_node(reused_node).is_some());
+        assert_eq!(scene_graph.get_children(root), vec![reused_node]);
+    }
+}
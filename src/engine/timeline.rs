@@ -0,0 +1,192 @@
+use crate::engine::audio_manager::AudioManager;
+use crate::engine::scene_graph::{NodeId, SceneGraph};
+use crate::engine::tween::{lerp_values, Easing, TweenProperty};
+
+// easing 決定從上一個關鍵影格到這一個之間的變化曲線
+#[derive(Clone, Copy)]
+pub struct Keyframe<T> {
+    pub time: f32,
+    pub value: T,
+    pub easing: Easing,
+}
+
+// 畫面中央對準的座標與縮放倍率
+#[derive(Clone, Copy)]
+pub struct CameraShot {
+    pub position: (f32, f32),
+    pub zoom: f32,
+}
+
+#[derive(Clone)]
+pub enum AudioEvent {
+    Music(String),
+    Sound(String),
+    Voice(String),
+}
+
+#[derive(Clone)]
+pub struct AudioCue {
+    pub time: f32,
+    pub event: AudioEvent,
+}
+
+pub enum Track {
+    // 精靈與文字都以這種軌道在節點的元件上內插同一種屬性，文字的逐字顯示用 TextReveal
+    Property { node_id: NodeId, keyframes: Vec<Keyframe<TweenProperty>> },
+    // 移動與縮放整個節點，鏡頭內的物件都掛在這個節點下
+    Camera { node_id: NodeId, view_center: (f32, f32), keyframes: Vec<Keyframe<CameraShot>> },
+    // 播放經過的時間點才觸發，拖曳時跳過的聲音不會補播
+    Audio { cues: Vec<AudioCue>, next_cue_index: usize },
+}
+
+impl Track {
+    pub fn property(node_id: NodeId, mut keyframes: Vec<Keyframe<TweenProperty>>) -> Self {
+        keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Track::Property { node_id, keyframes }
+    }
+
+    pub fn camera(node_id: NodeId, view_center: (f32, f32), mut keyframes: Vec<Keyframe<CameraShot>>) -> Self {
+        keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Track::Camera { node_id, view_center, keyframes }
+    }
+
+    pub fn audio(mut cues: Vec<AudioCue>) -> Self {
+        cues.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Track::Audio { cues, next_cue_index: 0 }
+    }
+
+    fn get_end_time(&self) -> f32 {
+        match self {
+            Track::Property { keyframes, .. } => keyframes.last().map_or(0.0, |keyframe| keyframe.time),
+            Track::Camera { keyframes, .. } => keyframes.last().map_or(0.0, |keyframe| keyframe.time),
+            Track::Audio { cues, .. } => cues.last().map_or(0.0, |cue| cue.time),
+        }
+    }
+
+    fn apply(&self, scene_graph: &mut SceneGraph, time: f32) {
+        match self {
+            Track::Property { node_id, keyframes } => {
+                let Some(value) = sample(keyframes, time, TweenProperty::get_target_value) else {
+                    return;
+                };
+                keyframes[0].value.set_value(scene_graph, *node_id, value);
+            },
+            Track::Camera { node_id, view_center, keyframes } => {
+                let Some((x, y, zoom, _)) = sample(keyframes, time, |shot| (shot.position.0, shot.position.1, shot.zoom, 0.0)) else {
+                    return;
+                };
+                if let Some(node) = scene_graph.get_node_mut(*node_id) {
+                    node.transform.scale = (zoom, zoom);
+                    node.transform.position = (view_center.0 - x * zoom, view_center.1 - y * zoom);
+                }
+            },
+            Track::Audio { .. } => { /* Empty */ },
+        }
+    }
+}
+
+// 第一個關鍵影格之前維持第一個值，最後一個之後維持最後一個值
+fn sample<T>(
+    keyframes: &[Keyframe<T>],
+    time: f32,
+    get_values: impl Fn(&T) -> (f32, f32, f32, f32)) -> Option<(f32, f32, f32, f32)> {
+    match keyframes.iter().position(|keyframe| keyframe.time > time) {
+        None => keyframes.last().map(|keyframe| get_values(&keyframe.value)),
+        Some(0) => Some(get_values(&keyframes[0].value)),
+        Some(next_index) => {
+            let previous_keyframe = &keyframes[next_index - 1];
+            let next_keyframe = &keyframes[next_index];
+            let progress = (time - previous_keyframe.time) / (next_keyframe.time - previous_keyframe.time);
+            Some(lerp_values(
+                get_values(&previous_keyframe.value),
+                get_values(&next_keyframe.value),
+                next_keyframe.easing.apply(progress)))
+        },
+    }
+}
+
+// 以絕對時間排列的多條軌道，任何時間點的畫面都只由時間決定，因此可以任意拖曳
+pub struct Timeline {
+    tracks: Vec<Track>,
+    duration: f32,
+    current_time: f32,
+    is_paused: bool,
+}
+
+impl Timeline {
+    pub fn new(tracks: Vec<Track>) -> Self {
+        let duration = tracks.iter().map(Track::get_end_time).fold(0.0, f32::max);
+        Self {
+            tracks,
+            duration,
+            current_time: 0.0,
+            is_paused: false,
+        }
+    }
+
+    pub fn get_current_time(&self) -> f32 {
+        self.current_time
+    }
+
+    pub fn get_duration(&self) -> f32 {
+        self.duration
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    pub fn set_paused(&mut self, is_paused: bool) {
+        self.is_paused = is_paused;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current_time >= self.duration
+    }
+
+    pub fn update(&mut self, scene_graph: &mut SceneGraph, audio_manager: &mut AudioManager, delta_time: f32) {
+        if !self.is_paused {
+            self.current_time = (self.current_time + delta_time).min(self.duration);
+        }
+
+        for track in &mut self.tracks {
+            let Track::Audio { cues, next_cue_index } = track else {
+                continue;
+            };
+            while let Some(cue) = cues.get(*next_cue_index).filter(|cue| cue.time <= self.current_time) {
+                match &cue.event {
+                    AudioEvent::Music(file_path) => {
+                        audio_manager.load_music(file_path);
+                        audio_manager.play_music();
+                    },
+                    AudioEvent::Sound(file_path) => audio_manager.play_sound_one_shot(file_path),
+                    AudioEvent::Voice(file_path) => {
+                        audio_manager.play_voice(file_path);
+                    },
+                }
+                *next_cue_index += 1;
+            }
+        }
+        self.apply(scene_graph);
+    }
+
+    pub fn seek(&mut self, scene_graph: &mut SceneGraph, time: f32) {
+        self.current_time = time.clamp(0.0, self.duration);
+        for track in &mut self.tracks {
+            if let Track::Audio { cues, next_cue_index } = track {
+                *next_cue_index = cues.iter().position(|cue| cue.time > self.current_time).unwrap_or(cues.len());
+            }
+        }
+        self.apply(scene_graph);
+    }
+
+    pub fn skip(&mut self, scene_graph: &mut SceneGraph) {
+        self.seek(scene_graph, self.duration);
+    }
+
+    fn apply(&self, scene_graph: &mut SceneGraph) {
+        for track in &self.tracks {
+            track.apply(scene_graph, self.current_time);
+        }
+    }
+}
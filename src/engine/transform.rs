@@ -0,0 +1,30 @@
+use nalgebra_glm::{Mat4, Vec3};
+
+// 以物件範圍內的 pivot 為中心縮放與旋轉，再整體位移；pivot 以範圍的比例表示，(0.5, 0.5) 為正中央
+#[derive(Clone, Copy)]
+pub struct Transform {
+    pub position: (f32, f32),
+    pub scale: (f32, f32),
+    pub rotation: f32,
+    pub pivot: (f32, f32),
+}
+
+impl Transform {
+    pub fn new() -> Self {
+        Self {
+            position: (0.0, 0.0),
+            scale: (1.0, 1.0),
+            rotation: 0.0,
+            pivot: (0.5, 0.5),
+        }
+    }
+
+    pub fn get_model_matrix(&self, rect: (f32, f32, f32, f32)) -> Mat4 {
+        let pivot_position = Vec3::new(rect.0 + rect.2 * self.pivot.0, rect.1 + rect.3 * self.pivot.1, 0.0);
+        nalgebra_glm::translation(&Vec3::new(self.position.0, self.position.1, 0.0)) *
+            nalgebra_glm::translation(&pivot_position) *
+            nalgebra_glm::rotation(self.rotation, &Vec3::z()) *
+            nalgebra_glm::scaling(&Vec3::new(self.scale.0, self.scale.1, 1.0)) *
+            nalgebra_glm::translation(&-pivot_position)
+    }
+}
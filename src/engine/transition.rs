@@ -0,0 +1,26 @@
+// 轉場時間的前半段蓋上畫面，在全黑時切換場景，後半段再掀開
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Transition {
+    None,
+    Fade(f32),
+    Wipe(f32),
+}
+
+impl Transition {
+    pub fn get_duration(&self) -> f32 {
+        match self {
+            Transition::None => 0.0,
+            Transition::Fade(duration) | Transition::Wipe(duration) => *duration,
+        }
+    }
+
+    pub fn get_cover_ratio(&self, elapsed_time: f32) -> f32 {
+        let duration = self.get_duration();
+        if duration <= 0.0 {
+            return 0.0;
+        }
+
+        let progress = (elapsed_time / duration).clamp(0.0, 1.0);
+        if progress < 0.5 { progress * 2.0 } else { (1.0 - progress) * 2.0 }
+    }
+}
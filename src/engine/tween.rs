@@ -0,0 +1,293 @@
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use crate::engine::drawable_implements::plane::Plane;
+use crate::engine::drawable_implements::text::Text;
+use crate::engine::scene_graph::{NodeId, SceneGraph};
+
+const BACK_C1: f32 = 1.70158;
+const BACK_C2: f32 = BACK_C1 * 1.525;
+const BACK_C3: f32 = BACK_C1 + 1.0;
+const ELASTIC_C4: f32 = 2.0 * PI / 3.0;
+const ELASTIC_C5: f32 = 2.0 * PI / 4.5;
+
+#[derive(Clone, Copy)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+}
+
+impl Easing {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "linear" => Some(Easing::Linear),
+            "quad_in" => Some(Easing::QuadIn),
+            "quad_out" => Some(Easing::QuadOut),
+            "quad_in_out" => Some(Easing::QuadInOut),
+            "cubic_in" => Some(Easing::CubicIn),
+            "cubic_out" => Some(Easing::CubicOut),
+            "cubic_in_out" => Some(Easing::CubicInOut),
+            "back_in" => Some(Easing::BackIn),
+            "back_out" => Some(Easing::BackOut),
+            "back_in_out" => Some(Easing::BackInOut),
+            "elastic_in" => Some(Easing::ElasticIn),
+            "elastic_out" => Some(Easing::ElasticOut),
+            "elastic_in_out" => Some(Easing::ElasticInOut),
+            _ => None,
+        }
+    }
+
+    // progress 為 0 到 1，back 與 elastic 的中間值會超出 0 到 1，但終點一定是 1
+    pub fn apply(&self, progress: f32) -> f32 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t).powi(2),
+            Easing::QuadInOut =>
+                if t < 0.5 { 2.0 * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(2) / 2.0 },
+            Easing::CubicIn => t.powi(3),
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut =>
+                if t < 0.5 { 4.0 * t.powi(3) } else { 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0 },
+            Easing::BackIn => BACK_C3 * t.powi(3) - BACK_C1 * t * t,
+            Easing::BackOut => 1.0 + BACK_C3 * (t - 1.0).powi(3) + BACK_C1 * (t - 1.0).powi(2),
+            Easing::BackInOut =>
+                if t < 0.5 { (2.0 * t).powi(2) * ((BACK_C2 + 1.0) * 2.0 * t - BACK_C2) / 2.0 }
+                else { ((2.0 * t - 2.0).powi(2) * ((BACK_C2 + 1.0) * (2.0 * t - 2.0) + BACK_C2) + 2.0) / 2.0 },
+            Easing::ElasticIn =>
+                if t <= 0.0 || t >= 1.0 { t }
+                else { -(2.0_f32.powf(10.0 * t - 10.0)) * ((10.0 * t - 10.75) * ELASTIC_C4).sin() },
+            Easing::ElasticOut =>
+                if t <= 0.0 || t >= 1.0 { t }
+                else { 2.0_f32.powf(-10.0 * t) * ((10.0 * t - 0.75) * ELASTIC_C4).sin() + 1.0 },
+            Easing::ElasticInOut =>
+                if t <= 0.0 || t >= 1.0 { t }
+                else if t < 0.5 { -(2.0_f32.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * ELASTIC_C5).sin()) / 2.0 }
+                else { 2.0_f32.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * ELASTIC_C5).sin() / 2.0 + 1.0 },
+        }
+    }
+}
+
+// 要動畫的屬性與目標值，作用在節點上的 Plane 或 Text 元件；位置與縮放改的是元件自己的 transform
+#[derive(Clone, Copy)]
+pub enum TweenProperty {
+    Alpha(f32),
+    Position((f32, f32)),
+    Scale((f32, f32)),
+    Color((f32, f32, f32, f32)),
+    TextReveal(f32),
+}
+
+impl TweenProperty {
+    pub(crate) fn get_target_value(&self) -> (f32, f32, f32, f32) {
+        match *self {
+            TweenProperty::Alpha(alpha) => (alpha, 0.0, 0.0, 0.0),
+            TweenProperty::Position(position) => (position.0, position.1, 0.0, 0.0),
+            TweenProperty::Scale(scale) => (scale.0, scale.1, 0.0, 0.0),
+            TweenProperty::Color(color) => color,
+            TweenProperty::TextReveal(end_range_ratio) => (end_range_ratio, 0.0, 0.0, 0.0),
+        }
+    }
+
+    fn get_value(&self, scene_graph: &SceneGraph, node_id: NodeId) -> Option<(f32, f32, f32, f32)> {
+        let plane = scene_graph.get_component::<Plane>(node_id);
+        let text = scene_graph.get_component::<Text>(node_id);
+        let transform = plane.map(|plane| &plane.transform).or(text.map(|text| &text.transform));
+        match self {
+            TweenProperty::Alpha(_) => plane.map(Plane::get_alpha).or(text.map(Text::get_alpha))
+                .map(|alpha| (alpha, 0.0, 0.0, 0.0)),
+            TweenProperty::Position(_) => transform.map(|transform| (transform.position.0, transform.position.1, 0.0, 0.0)),
+            TweenProperty::Scale(_) => transform.map(|transform| (transform.scale.0, transform.scale.1, 0.0, 0.0)),
+            TweenProperty::Color(_) => plane.map(Plane::get_color).or(text.map(Text::get_color)),
+            TweenProperty::TextReveal(_) => text.map(|text| (text.end_range_ratio, 0.0, 0.0, 0.0)),
+        }
+    }
+
+    pub(crate) fn set_value(&self, scene_graph: &mut SceneGraph, node_id: NodeId, value: (f32, f32, f32, f32)) {
+        if let Some(plane) = scene_graph.get_component_mut::<Plane>(node_id) {
+            match self {
+                TweenProperty::Alpha(_) => plane.set_alpha(value.0),
+                TweenProperty::Position(_) => plane.transform.position = (value.0, value.1),
+                TweenProperty::Scale(_) => plane.transform.scale = (value.0, value.1),
+                TweenProperty::Color(_) => plane.set_color(value),
+                TweenProperty::TextReveal(_) => { /* Empty */ },
+            }
+        } else if let Some(text) = scene_graph.get_component_mut::<Text>(node_id) {
+            match self {
+                TweenProperty::Alpha(_) => text.set_alpha(value.0),
+                TweenProperty::Position(_) => text.transform.position = (value.0, value.1),
+                TweenProperty::Scale(_) => text.transform.scale = (value.0, value.1),
+                TweenProperty::Color(_) => text.set_color(value),
+                TweenProperty::TextReveal(_) => text.set_range(value.0),
+            }
+        }
+    }
+}
+
+pub(crate) fn lerp_values(start: (f32, f32, f32, f32), target: (f32, f32, f32, f32), progress: f32) -> (f32, f32, f32, f32) {
+    let lerp = |start: f32, target: f32| start + (target - start) * progress;
+    (lerp(start.0, target.0), lerp(start.1, target.1), lerp(start.2, target.2), lerp(start.3, target.3))
+}
+
+pub struct Tween {
+    node_id: NodeId,
+    property: TweenProperty,
+    duration: f32,
+    easing: Easing,
+    elapsed_time: f32,
+    start_value: Option<(f32, f32, f32, f32)>,
+}
+
+impl Tween {
+    // 回傳 Some(用剩的時間) 表示已經結束
+    fn advance(&mut self, scene_graph: &mut SceneGraph, delta_time: f32) -> Option<f32> {
+        // 起始值在開始播放時才讀取，排在延遲或其他動畫後面也會從當下的值接著動
+        if self.start_value.is_none() {
+            self.start_value = self.property.get_value(scene_graph, self.node_id);
+        }
+        // 節點上沒有對應的元件時直接視為完成
+        let Some(start_value) = self.start_value else {
+            return Some(delta_time);
+        };
+
+        // 長度為 0、負數或不是有限值時直接跳到目標值，避免算出 NaN
+        if !(self.duration.is_finite() && self.duration > 0.0) {
+            self.property.set_value(scene_graph, self.node_id, self.property.get_target_value());
+            return Some(delta_time);
+        }
+
+        self.elapsed_time += delta_time;
+        let progress = (self.elapsed_time / self.duration).min(1.0);
+        let value = lerp_values(start_value, self.property.get_target_value(), self.easing.apply(progress));
+        self.property.set_value(scene_graph, self.node_id, value);
+
+        (self.elapsed_time >= self.duration).then_some(self.elapsed_time - self.duration)
+    }
+}
+
+type AnimationCallback = Box<dyn FnOnce(&mut SceneGraph)>;
+
+pub enum Animation {
+    Tween(Tween),
+    Delay(f32),
+    Sequence(VecDeque<Animation>),
+    Parallel(Vec<Animation>),
+    Callback(Option<AnimationCallback>),
+}
+
+impl Animation {
+    pub fn tween(node_id: NodeId, property: TweenProperty, duration: f32, easing: Easing) -> Self {
+        Animation::Tween(Tween {
+            node_id,
+            property,
+            duration,
+            easing,
+            elapsed_time: 0.0,
+            start_value: None,
+        })
+    }
+
+    pub fn delay(seconds: f32) -> Self {
+        Animation::Delay(seconds)
+    }
+
+    pub fn sequence(animations: Vec<Animation>) -> Self {
+        Animation::Sequence(animations.into())
+    }
+
+    pub fn parallel(animations: Vec<Animation>) -> Self {
+        Animation::Parallel(animations)
+    }
+
+    pub fn callback(callback: impl FnOnce(&mut SceneGraph) + 'static) -> Self {
+        Animation::Callback(Some(Box::new(callback)))
+    }
+
+    // 回傳 Some(用剩的時間) 表示已經結束，序列會把用剩的時間交給下一段
+    fn advance(&mut self, scene_graph: &mut SceneGraph, delta_time: f32) -> Option<f32> {
+        match self {
+            Animation::Tween(tween) => tween.advance(scene_graph, delta_time),
+            Animation::Delay(remaining_time) => {
+                *remaining_time -= delta_time;
+                (*remaining_time <= 0.0).then_some(-*remaining_time)
+            },
+            Animation::Sequence(animations) => {
+                let mut remaining_delta_time = delta_time;
+                while let Some(animation) = animations.front_mut() {
+                    remaining_delta_time = animation.advance(scene_graph, remaining_delta_time)?;
+                    animations.pop_front();
+                }
+                Some(remaining_delta_time)
+            },
+            Animation::Parallel(animations) => {
+                let mut remaining_delta_time = delta_time;
+                animations.retain_mut(|animation| match animation.advance(scene_graph, delta_time) {
+                    Some(animation_remaining_delta_time) => {
+                        remaining_delta_time = remaining_delta_time.min(animation_remaining_delta_time);
+                        false
+                    },
+                    None => true,
+                });
+                animations.is_empty().then_some(remaining_delta_time)
+            },
+            Animation::Callback(callback) => {
+                if let Some(callback) = callback.take() {
+                    callback(scene_graph);
+                }
+                Some(delta_time)
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AnimationId(usize);
+
+// 動畫的進度由場景每幀推進，屬性直接寫回場景圖上的元件
+pub struct TweenPlayer {
+    animations: Vec<(AnimationId, Animation)>,
+    next_id: usize,
+}
+
+impl TweenPlayer {
+    pub fn new() -> Self {
+        Self {
+            animations: vec![],
+            next_id: 0,
+        }
+    }
+
+    pub fn play(&mut self, animation: Animation) -> AnimationId {
+        let animation_id = AnimationId(self.next_id);
+        self.next_id += 1;
+        self.animations.push((animation_id, animation));
+        animation_id
+    }
+
+    pub fn is_playing(&self, animation_id: AnimationId) -> bool {
+        self.animations.iter().any(|(id, _)| *id == animation_id)
+    }
+
+    pub fn update(&mut self, scene_graph: &mut SceneGraph, delta_time: f32) {
+        self.animations.retain_mut(|(_, animation)| animation.advance(scene_graph, delta_time).is_none());
+    }
+
+    // 直接跳到結尾，途中的回呼都會被呼叫
+    pub fn complete(&mut self, scene_graph: &mut SceneGraph, animation_id: AnimationId) {
+        if let Some(index) = self.animations.iter().position(|(id, _)| *id == animation_id) {
+            let (_, mut animation) = self.animations.remove(index);
+            animation.advance(scene_graph, f32::INFINITY);
+        }
+    }
+}
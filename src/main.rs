@@ -3,10 +3,17 @@ mod r00_avg_game;
 
 use std::cell::RefCell;
 use std::rc::Rc;
-use engine::game::Game;
-use engine::scene::Scene;
+use engine::clock::{Clock, ManualClock, RealClock};
+use engine::design_resolution::DesignResolution;
+use engine::drawable_implements::text_benchmark::run_text_benchmark;
+use engine::game::{Game, FRAGMENT_SHADER, VERTEX_SHADER};
+use crate::r00_avg_game::common::FONT_PATH;
 use crate::r00_avg_game::data::avg_item::{AvgItem, SelectionItem};
-use crate::r00_avg_game::gameplay::GamePlayScene;
+use crate::r00_avg_game::data::read_history::{ReadHistory, READ_HISTORY_PATH};
+use crate::r00_avg_game::data::settings::{Settings, SETTINGS_PATH};
+use crate::r00_avg_game::data::story_context::StoryContext;
+use crate::r00_avg_game::settings_menu::apply_settings;
+use crate::r00_avg_game::title::{TitleConfig, TitleEntry, TitleScene};
 
 fn main() {
     #[cfg(target_arch = "wasm32")]
@@ -14,154 +21,232 @@ fn main() {
     #[cfg(not(target_arch = "wasm32"))]
     let is_web = false;
 
-    let mut game = Game::new("AVG Game", 960, 540, is_web);
-    let mut scene = GamePlayScene::new(
-        vec![
-            AvgItem {
-                background_music: Some("./resources/musics/background.mp3".to_string()),
-                background_image_path: Some("./resources/images/background001.png".to_string()),
-                center_character_image_path: Some("./resources/images/characters/Yuuta1/normal.png".to_string()),
-                character_name: Some("悠太翼".to_string()),
-                content: Some("今天又是美好的一天呢！".to_string()),
-                selection_items: Some(vec![
-                    SelectionItem {
-                        content: "怎麼了？看起來心情不錯！".to_string(),
-                        next_index: 1
-                    },
-                    SelectionItem {
-                        content: "還是跟昨天一樣普通吧。".to_string(),
-                        next_index: 2
-                    }
-                ]),
-                next_index: None,
-            },
-            AvgItem {
-                background_music: None,
-                background_image_path: None,
-                center_character_image_path: Some("./resources/images/characters/Yuuta1/happy.png".to_string()),
-                character_name: Some("悠太翼".to_string()),
-                content: Some("你居然看得出來！對，我今天超開心的！".to_string()),
-                selection_items: None,
-                next_index: Some(3),
-            },
-            AvgItem {
-                background_music: None,
-                background_image_path: None,
-                center_character_image_path: Some("./resources/images/characters/Yuuta1/sad.png".to_string()),
-                character_name: Some("悠太翼".to_string()),
-                content: Some("唉，其實今天也沒什麼特別的……".to_string()),
-                selection_items: None,
-                next_index: Some(3),
-            },
-            AvgItem {
-                background_music: None,
-                background_image_path: None,
-                center_character_image_path: Some("./resources/images/characters/Yuuta1/normal.png".to_string()),
-                character_name: Some("悠太翼".to_string()),
-                content: Some("總之，今天的任務就這麼完成了！".to_string()),
-                selection_items: Some(vec![
-                    SelectionItem {
-                        content: "恭喜你！".to_string(),
-                        next_index: 4
-                    },
-                    SelectionItem {
-                        content: "不就是日常嘛。".to_string(),
-                        next_index: 5
-                    }
-                ]),
-                next_index: None,
-            },
-            AvgItem {
-                background_music: None,
-                background_image_path: None,
-                center_character_image_path: Some("./resources/images/characters/Yuuta1/happy.png".to_string()),
-                character_name: Some("悠太翼".to_string()),
-                content: Some("謝謝！你真的是個好夥伴！".to_string()),
-                selection_items: None,
-                next_index: Some(6),
-            },
-            AvgItem {
-                background_music: None,
-                background_image_path: None,
-                center_character_image_path: Some("./resources/images/characters/Yuuta1/sad.png".to_string()),
-                character_name: Some("悠太翼".to_string()),
-                content: Some("唉，或許你說得對。".to_string()),
-                selection_items: None,
-                next_index: None,
-            },
-            AvgItem {
-                background_music: None,
-                background_image_path: None,
-                center_character_image_path: Some("./resources/images/characters/Yuuta1/normal.png".to_string()),
-                character_name: Some("悠太翼".to_string()),
-                content: Some("對了，你喜歡烤肉嗎？".to_string()),
-                selection_items: Some(vec![
-                    SelectionItem {
-                        content: "當然！烤肉超好吃！".to_string(),
-                        next_index: 7
-                    },
-                    SelectionItem {
-                        content: "還好，我更喜歡別的料理。".to_string(),
-                        next_index: 8
-                    }
-                ]),
-                next_index: None,
-            },
-            AvgItem {
-                background_music: None,
-                background_image_path: None,
-                center_character_image_path: Some("./resources/images/characters/Yuuta1/happy.png".to_string()),
-                character_name: Some("悠太翼".to_string()),
-                content: Some("我就知道你跟我一樣！烤肉最棒了，特別是和朋友們一起烤！".to_string()),
-                selection_items: None,
-                next_index: Some(9),
-            },
-            AvgItem {
-                background_music: None,
-                background_image_path: None,
-                center_character_image_path: Some("./resources/images/characters/Yuuta1/normal.png".to_string()),
-                character_name: Some("悠太翼".to_string()),
-                content: Some("真的嗎？烤肉可是超多人喜歡的呢。不過每個人喜好不同嘛～".to_string()),
-                selection_items: None,
-                next_index: Some(9),
-            },
-            AvgItem {
-                background_music: None,
-                background_image_path: None,
-                center_character_image_path: Some("./resources/images/characters/Yuuta1/normal.png".to_string()),
-                character_name: Some("悠太翼".to_string()),
-                content: Some("你最喜歡的烤肉食材是什麼呢？".to_string()),
-                selection_items: Some(vec![
-                    SelectionItem {
-                        content: "牛肉".to_string(),
-                        next_index: 10
-                    },
-                    SelectionItem {
-                        content: "豬肉".to_string(),
-                        next_index: 10
-                    },
-                    SelectionItem {
-                        content: "海鮮".to_string(),
-                        next_index: 10
-                    },
-                    SelectionItem {
-                        content: "蔬菜".to_string(),
-                        next_index: 10
-                    }
-                ]),
-                next_index: None,
-            },
-            AvgItem {
-                background_music: None,
-                background_image_path: None,
-                center_character_image_path: Some("./resources/images/characters/Yuuta1/happy.png".to_string()),
-                character_name: Some("悠太翼".to_string()),
-                content: Some("嗯嗯，那是我的最愛之一呢！下次一起烤吧～".to_string()),
-                selection_items: None,
-                next_index: None,
-            }
-        ]
-    );
-    game.load_scene(Rc::new(RefCell::new(scene)));
+    // 以 --layout=4:3 或 --layout=9:16 啟動可切換成其他版面
+    let design_resolution = get_arg_value("--layout=")
+        .and_then(|value| DesignResolution::from_name(&value))
+        .unwrap_or(DesignResolution::LANDSCAPE_16_9);
+    let (window_width, window_height) = design_resolution.scaled(0.5);
+    // --frame-time=0.0166 讓每幀固定前進指定秒數，不受實際經過時間影響
+    let clock: Box<dyn Clock> = match get_arg_value("--frame-time=").and_then(|value| value.parse().ok()) {
+        Some(frame_time) => Box::new(ManualClock::new(frame_time)),
+        None => Box::new(RealClock::new()),
+    };
+    let mut game = match Game::new("AVG Game", window_width, window_height, design_resolution, is_web, clock) {
+        Ok(game) => game,
+        Err(error) => {
+            eprintln!("Can't start the game: {}", error);
+            std::process::exit(1);
+        }
+    };
+    // --fps=0 不限制幀率，--vsync=off 關閉垂直同步，--fixed-update=60 以每秒 60 次固定步進更新
+    if let Some(target_fps) = get_arg_value("--fps=").and_then(|value| value.parse().ok()) {
+        game.set_target_fps(target_fps);
+    }
+    if get_arg_value("--vsync=").as_deref() == Some("off") {
+        game.set_vsync(false);
+    }
+    if let Some(updates_per_second) = get_arg_value("--fixed-update=").and_then(|value| value.parse().ok()) {
+        game.set_fixed_update_rate(updates_per_second);
+    }
+    // --log-textures=on 在每次切換場景時列出仍在使用的貼圖
+    if get_arg_value("--log-textures=").as_deref() == Some("on") {
+        game.set_texture_logging(true);
+    }
+    // --benchmark-text=600 以指定幀數比較字型快取前後逐字顯示的耗時，結束後直接離開
+    if let Some(frame_count) = get_arg_value("--benchmark-text=").and_then(|value| value.parse().ok()) {
+        run_text_benchmark(
+            &game.drawable_generator,
+            FONT_PATH,
+            60,
+            &"今天又是美好的一天呢！你居然看得出來！對，我今天超開心的！".to_string(),
+            frame_count,
+            VERTEX_SHADER,
+            FRAGMENT_SHADER);
+        return;
+    }
+    let settings = Settings::load(SETTINGS_PATH);
+    apply_settings(&mut game, &settings);
+    let settings = Rc::new(RefCell::new(settings));
+    let read_history = Rc::new(RefCell::new(ReadHistory::load(READ_HISTORY_PATH)));
+    let avg_items = vec![
+        AvgItem {
+            background_music: Some("./resources/musics/background.mp3".to_string()),
+            background_image_path: Some("./resources/images/background001.png".to_string()),
+            center_character_image_path: Some("./resources/images/characters/Yuuta1/normal.png".to_string()),
+            character_name: Some("悠太翼".to_string()),
+            content: Some("今天又是美好的一天呢！".to_string()),
+            voice_path: None,
+            selection_items: Some(vec![
+                SelectionItem {
+                    content: "怎麼了？看起來心情不錯！".to_string(),
+                    next_index: 1
+                },
+                SelectionItem {
+                    content: "還是跟昨天一樣普通吧。".to_string(),
+                    next_index: 2
+                }
+            ]),
+            next_index: None,
+        },
+        AvgItem {
+            background_music: None,
+            background_image_path: None,
+            center_character_image_path: Some("./resources/images/characters/Yuuta1/happy.png".to_string()),
+            character_name: Some("悠太翼".to_string()),
+            content: Some("你居然看得出來！對，我今天超開心的！".to_string()),
+            voice_path: None,
+            selection_items: None,
+            next_index: Some(3),
+        },
+        AvgItem {
+            background_music: None,
+            background_image_path: None,
+            center_character_image_path: Some("./resources/images/characters/Yuuta1/sad.png".to_string()),
+            character_name: Some("悠太翼".to_string()),
+            content: Some("唉，其實今天也沒什麼特別的……".to_string()),
+            voice_path: None,
+            selection_items: None,
+            next_index: Some(3),
+        },
+        AvgItem {
+            background_music: None,
+            background_image_path: None,
+            center_character_image_path: Some("./resources/images/characters/Yuuta1/normal.png".to_string()),
+            character_name: Some("悠太翼".to_string()),
+            content: Some("總之，今天的任務就這麼完成了！".to_string()),
+            voice_path: None,
+            selection_items: Some(vec![
+                SelectionItem {
+                    content: "恭喜你！".to_string(),
+                    next_index: 4
+                },
+                SelectionItem {
+                    content: "不就是日常嘛。".to_string(),
+                    next_index: 5
+                }
+            ]),
+            next_index: None,
+        },
+        AvgItem {
+            background_music: None,
+            background_image_path: None,
+            center_character_image_path: Some("./resources/images/characters/Yuuta1/happy.png".to_string()),
+            character_name: Some("悠太翼".to_string()),
+            content: Some("謝謝！你真的是個好夥伴！".to_string()),
+            voice_path: None,
+            selection_items: None,
+            next_index: Some(6),
+        },
+        AvgItem {
+            background_music: None,
+            background_image_path: None,
+            center_character_image_path: Some("./resources/images/characters/Yuuta1/sad.png".to_string()),
+            character_name: Some("悠太翼".to_string()),
+            content: Some("唉，或許你說得對。".to_string()),
+            voice_path: None,
+            selection_items: None,
+            next_index: None,
+        },
+        AvgItem {
+            background_music: None,
+            background_image_path: None,
+            center_character_image_path: Some("./resources/images/characters/Yuuta1/normal.png".to_string()),
+            character_name: Some("悠太翼".to_string()),
+            content: Some("對了，你喜歡烤肉嗎？".to_string()),
+            voice_path: None,
+            selection_items: Some(vec![
+                SelectionItem {
+                    content: "當然！烤肉超好吃！".to_string(),
+                    next_index: 7
+                },
+                SelectionItem {
+                    content: "還好，我更喜歡別的料理。".to_string(),
+                    next_index: 8
+                }
+            ]),
+            next_index: None,
+        },
+        AvgItem {
+            background_music: None,
+            background_image_path: None,
+            center_character_image_path: Some("./resources/images/characters/Yuuta1/happy.png".to_string()),
+            character_name: Some("悠太翼".to_string()),
+            content: Some("我就知道你跟我一樣！烤肉最棒了，特別是和朋友們一起烤！".to_string()),
+            voice_path: None,
+            selection_items: None,
+            next_index: Some(9),
+        },
+        AvgItem {
+            background_music: None,
+            background_image_path: None,
+            center_character_image_path: Some("./resources/images/characters/Yuuta1/normal.png".to_string()),
+            character_name: Some("悠太翼".to_string()),
+            content: Some("真的嗎？烤肉可是超多人喜歡的呢。不過每個人喜好不同嘛～".to_string()),
+            voice_path: None,
+            selection_items: None,
+            next_index: Some(9),
+        },
+        AvgItem {
+            background_music: None,
+            background_image_path: None,
+            center_character_image_path: Some("./resources/images/characters/Yuuta1/normal.png".to_string()),
+            character_name: Some("悠太翼".to_string()),
+            content: Some("你最喜歡的烤肉食材是什麼呢？".to_string()),
+            voice_path: None,
+            selection_items: Some(vec![
+                SelectionItem {
+                    content: "牛肉".to_string(),
+                    next_index: 10
+                },
+                SelectionItem {
+                    content: "豬肉".to_string(),
+                    next_index: 10
+                },
+                SelectionItem {
+                    content: "海鮮".to_string(),
+                    next_index: 10
+                },
+                SelectionItem {
+                    content: "蔬菜".to_string(),
+                    next_index: 10
+                }
+            ]),
+            next_index: None,
+        },
+        AvgItem {
+            background_music: None,
+            background_image_path: None,
+            center_character_image_path: Some("./resources/images/characters/Yuuta1/happy.png".to_string()),
+            character_name: Some("悠太翼".to_string()),
+            content: Some("嗯嗯，那是我的最愛之一呢！下次一起烤吧～".to_string()),
+            voice_path: None,
+            selection_items: None,
+            next_index: None,
+        }
+    ];
+    let story_context = StoryContext {
+        avg_items: Rc::new(avg_items),
+        settings,
+        read_history,
+    };
+    let title_config = TitleConfig {
+        background_image_path: Some("./resources/images/background001.png".to_string()),
+        logo_image_path: None,
+        title: Some("AVG Game".to_string()),
+        background_music: Some("./resources/musics/background.mp3".to_string()),
+        opening_cutscene_path: Some("./resources/cutscenes/opening.txt".to_string()),
+        entries: vec![
+            TitleEntry::NewGame,
+            TitleEntry::Continue,
+            TitleEntry::Load,
+            TitleEntry::Settings,
+            TitleEntry::Quit,
+        ],
+    };
+    game.load_scene(Rc::new(RefCell::new(TitleScene::new(story_context, title_config))));
     game.run();
-}
\ No newline at end of file
+}
+
+fn get_arg_value(prefix: &str) -> Option<String> {
+    std::env::args().find_map(|arg| arg.strip_prefix(prefix).map(|value| value.to_string()))
+}
@@ -0,0 +1,122 @@
+use crate::engine::drawable_implements::generator::ShaderSource;
+use crate::engine::game::{Game, DEFAULT_SHADER_NAME};
+use crate::engine::input::action::Action;
+use crate::engine::input::input_state::InputState;
+use crate::engine::scene::Scene;
+use crate::engine::scene_graph::{NodeId, SceneGraph};
+use crate::r00_avg_game::common::{FONT_PATH, HIGHLIGHT_COLOR, NORMAL_COLOR, OVERLAY_BACKGROUND_COLOR, OVERLAY_CLOSE_TRANSITION};
+use crate::r00_avg_game::layout::Layout;
+
+const BACKLOG_VISIBLE_COUNT: usize = 4;
+
+pub struct BacklogScene {
+    lines: Vec<String>,
+    scroll: usize,
+    is_dirty: bool,
+    scene_graph: SceneGraph,
+    background_node: NodeId,
+    line_texts_node: NodeId,
+}
+
+impl BacklogScene {
+    pub(crate) fn new(lines: Vec<String>) -> Self {
+        let mut scene_graph = SceneGraph::new();
+        let root = scene_graph.get_root();
+        let background_node = scene_graph.add_node(root, None);
+        let line_texts_node = scene_graph.add_node(root, None);
+        Self {
+            lines,
+            scroll: 0,
+            is_dirty: true,
+            scene_graph,
+            background_node,
+            line_texts_node,
+        }
+    }
+
+    fn refresh_line_texts(&mut self, game: &Game, layout: &Layout) {
+        let end_index = self.lines.len() - self.scroll;
+        let start_index = end_index.saturating_sub(BACKLOG_VISIBLE_COUNT);
+        let line_texts = self.lines[start_index..end_index]
+            .iter().enumerate().map(|(row, line)| {
+                let mut line_text = game.drawable_generator.generate_text(
+                    (80.0 * layout.scale, layout.height - (200.0 + 220.0 * row as f32) * layout.scale),
+                    -0.7,
+                    line,
+                    1.0,
+                    NORMAL_COLOR,
+                    FONT_PATH,
+                    layout.scale_font_size(48),
+                    ShaderSource::Name(DEFAULT_SHADER_NAME)).map_err(|error| eprintln!("{}", error)).ok()?;
+                // 角色名稱以強調色顯示
+                if let Some(name_length) = line.find('「').filter(|_| line.ends_with('」')) {
+                    line_text.set_glyph_color(0..line[..name_length].chars().count(), HIGHLIGHT_COLOR);
+                }
+                Some(line_text)
+            }).collect::<Option<Vec<_>>>();
+        self.scene_graph.clear_children(self.line_texts_node);
+        for line_text in line_texts.unwrap_or_default() {
+            self.scene_graph.add_node(self.line_texts_node, Some(Box::new(line_text)));
+        }
+    }
+}
+
+impl Scene for BacklogScene {
+    fn update(&mut self, game: &mut Game, _delta_time: f32, input: &InputState) {
+        let layout = Layout::new(game.get_design_resolution());
+
+        if !self.scene_graph.has_component(self.background_node) {
+            let mut background_plane = game.drawable_generator.generate_plane_from_image(
+                layout.get_full_screen_rect(),
+                -0.6,
+                OVERLAY_BACKGROUND_COLOR,
+                None,
+                ShaderSource::Name(DEFAULT_SHADER_NAME)
+            ).map_err(|error| eprintln!("{}", error)).ok();
+            // 由下往上漸暗，越上面的紀錄越舊
+            if let Some(background_plane) = &mut background_plane {
+                background_plane.set_vertex_colors([
+                    (1.0, 1.0, 1.0, 0.8),
+                    (1.0, 1.0, 1.0, 0.8),
+                    (1.0, 1.0, 1.0, 1.0),
+                    (1.0, 1.0, 1.0, 1.0),
+                ]);
+            }
+            self.scene_graph.set_component(self.background_node, background_plane.map(|plane| Box::new(plane) as _));
+        }
+
+        if input.is_pressed(Action::Back) ||
+            input.is_pressed(Action::Menu) ||
+            input.is_pressed(Action::Advance) ||
+            input.is_pressed(Action::Backlog) {
+            game.pop_scene(OVERLAY_CLOSE_TRANSITION);
+            return;
+        }
+
+        let max_scroll = self.lines.len().saturating_sub(BACKLOG_VISIBLE_COUNT);
+        let previous_scroll = self.scroll;
+        if input.is_pressed(Action::Up) {
+            self.scroll = (self.scroll + 1).min(max_scroll);
+        }
+        if input.is_pressed(Action::Down) {
+            self.scroll = self.scroll.saturating_sub(1);
+        }
+
+        if self.is_dirty || self.scroll != previous_scroll {
+            self.refresh_line_texts(game, &layout);
+            self.is_dirty = false;
+        }
+    }
+
+    fn get_scene_graph(&self) -> Option<&SceneGraph> {
+        Some(&self.scene_graph)
+    }
+
+    fn get_scene_graph_mut(&mut self) -> Option<&mut SceneGraph> {
+        Some(&mut self.scene_graph)
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,36 @@
+use crate::engine::drawable_implements::text::Text;
+use crate::engine::input::binding::InputDevice;
+use crate::engine::input::input_state::InputState;
+use crate::engine::transition::Transition;
+
+pub const FONT_PATH: &str = "./resources/fonts/SourceHanSerifTC-Heavy.otf";
+pub const NORMAL_COLOR: (f32, f32, f32, f32) = (1.0, 1.0, 1.0, 1.0);
+pub const HIGHLIGHT_COLOR: (f32, f32, f32, f32) = (1.0, 1.0, 0.0, 1.0);
+pub const OVERLAY_BACKGROUND_COLOR: (f32, f32, f32, f32) = (0.0, 0.0, 0.0, 0.75);
+pub const TOUCH_TARGET_PADDING: f32 = 40.0;
+pub const CONFIRM_SOUND_FILE_PATH: &str = "resources/musics/confirm.mp3";
+pub const HOVER_SOUND_FILE_PATH: &str = "resources/musics/hover.wav";
+pub const OVERLAY_OPEN_TRANSITION: Transition = Transition::Wipe(0.4);
+pub const OVERLAY_CLOSE_TRANSITION: Transition = Transition::Fade(0.3);
+pub const SCENE_TRANSITION: Transition = Transition::Fade(1.0);
+
+pub fn get_hovered_index<'a>(texts: impl IntoIterator<Item = &'a Text>, input: &InputState) -> Option<usize> {
+    if input.last_device != InputDevice::Mouse {
+        return None;
+    }
+
+    let pointer_position = input.pointer_position?;
+    texts
+        .into_iter()
+        .position(|text| text.contains((pointer_position.0 as f32, pointer_position.1 as f32)))
+}
+
+pub fn highlight_texts<'a>(texts: impl IntoIterator<Item = &'a mut Text>, focused_index: Option<usize>) {
+    for (index, text) in texts.into_iter().enumerate() {
+        if Some(index) == focused_index {
+            text.set_color(HIGHLIGHT_COLOR);
+        } else {
+            text.set_color(NORMAL_COLOR);
+        }
+    }
+}
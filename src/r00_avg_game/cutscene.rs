@@ -0,0 +1,190 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::mem::{discriminant, Discriminant};
+use std::rc::Rc;
+use crate::engine::component::Component;
+use crate::engine::drawable_implements::generator::ShaderSource;
+use crate::engine::drawable_implements::plane::Plane;
+use crate::engine::game::{Game, DEFAULT_SHADER_NAME};
+use crate::engine::input::action::Action;
+use crate::engine::input::input_state::InputState;
+use crate::engine::scene::Scene;
+use crate::engine::scene_graph::{NodeId, SceneGraph};
+use crate::engine::timeline::{CameraShot, Keyframe, Timeline, Track};
+use crate::engine::tween::TweenProperty;
+use crate::r00_avg_game::common::{FONT_PATH, SCENE_TRANSITION};
+use crate::r00_avg_game::data::cutscene_script::{CutsceneKeyframe, CutsceneObject, CutsceneScript};
+use crate::r00_avg_game::data::story_context::StoryContext;
+use crate::r00_avg_game::gameplay::GamePlayScene;
+use crate::r00_avg_game::layout::Layout;
+
+const OBJECT_Z_STEP: f32 = 0.01;
+const SEEK_SECONDS: f32 = 2.0;
+const PROGRESS_BAR_HEIGHT: f32 = 8.0;
+// 腳本中的鏡頭座標以 1920x1080 的畫面中央為準
+const REFERENCE_VIEW_CENTER: (f32, f32) = (960.0, 540.0);
+
+// 依腳本播放開場動畫，播完或略過後進入遊戲
+pub struct CutsceneScene {
+    story_context: StoryContext,
+    script: CutsceneScript,
+    scene_graph: SceneGraph,
+    camera_node: NodeId,
+    progress_bar_node: NodeId,
+    timeline: Option<Timeline>,
+    is_finished: bool,
+}
+
+impl CutsceneScene {
+    pub(crate) fn new(story_context: StoryContext, script: CutsceneScript) -> Self {
+        let mut scene_graph = SceneGraph::new();
+        let root = scene_graph.get_root();
+        let camera_node = scene_graph.add_node(root, None);
+        let progress_bar_node = scene_graph.add_node(root, None);
+        Self {
+            story_context,
+            script,
+            scene_graph,
+            camera_node,
+            progress_bar_node,
+            timeline: None,
+            is_finished: false,
+        }
+    }
+
+    fn build_timeline(&mut self, game: &mut Game, layout: &Layout) -> Timeline {
+        let mut node_ids = HashMap::new();
+        for (index, object) in self.script.objects.iter().enumerate() {
+            // 先宣告的物件在後面
+            let z_index = -OBJECT_Z_STEP * index as f32;
+            // shader 建立失敗時節點沒有元件，關鍵影格仍然找得到物件
+            let (name, component): (&String, Option<Box<dyn Component>>) = match object {
+                CutsceneObject::Sprite { name, image_path } => (name, game.drawable_generator.generate_plane_from_image(
+                    layout.get_cover_rect(),
+                    z_index,
+                    (1.0, 1.0, 1.0, 1.0),
+                    Some(image_path),
+                    ShaderSource::Name(DEFAULT_SHADER_NAME))
+                    .map(|plane| Box::new(plane) as _)
+                    .map_err(|error| eprintln!("{}", error))
+                    .ok()),
+                CutsceneObject::Text { name, left_bottom, font_size, content } => (name, game.drawable_generator.generate_text(
+                    layout.scale_position(*left_bottom),
+                    z_index,
+                    content,
+                    1.0,
+                    (1.0, 1.0, 1.0, 1.0),
+                    FONT_PATH,
+                    layout.scale_font_size(*font_size),
+                    ShaderSource::Name(DEFAULT_SHADER_NAME))
+                    .map(|text| Box::new(text) as _)
+                    .map_err(|error| eprintln!("{}", error))
+                    .ok()),
+            };
+            node_ids.insert(name.as_str(), self.scene_graph.add_node(self.camera_node, component));
+        }
+
+        // 同一個物件的同一種屬性放在同一條軌道
+        let mut property_tracks: Vec<(NodeId, Discriminant<TweenProperty>, Vec<Keyframe<TweenProperty>>)> = vec![];
+        for CutsceneKeyframe { target, keyframe } in &self.script.keyframes {
+            let Some(node_id) = node_ids.get(target.as_str()).copied() else {
+                eprintln!("Unknown cutscene object: {}", target);
+                continue;
+            };
+            let value = match keyframe.value {
+                TweenProperty::Position(position) => TweenProperty::Position(layout.scale_position(position)),
+                value => value,
+            };
+            let keyframe = Keyframe { value, ..*keyframe };
+            let property_kind = discriminant(&keyframe.value);
+            match property_tracks.iter_mut().find(|(track_node_id, track_property_kind, _)| *track_node_id == node_id && *track_property_kind == property_kind) {
+                Some((_, _, keyframes)) => keyframes.push(keyframe),
+                None => property_tracks.push((node_id, property_kind, vec![keyframe])),
+            }
+        }
+
+        let mut tracks = property_tracks
+            .into_iter()
+            .map(|(node_id, _, keyframes)| Track::property(node_id, keyframes))
+            .collect::<Vec<_>>();
+        if !self.script.camera_keyframes.is_empty() {
+            let view_center = (layout.width / 2.0, layout.height / 2.0);
+            let camera_keyframes = self.script.camera_keyframes
+                .iter()
+                .map(|keyframe| {
+                    let (x, y) = layout.scale_position((
+                        keyframe.value.position.0 - REFERENCE_VIEW_CENTER.0,
+                        keyframe.value.position.1 - REFERENCE_VIEW_CENTER.1));
+                    Keyframe {
+                        value: CameraShot { position: (view_center.0 + x, view_center.1 + y), zoom: keyframe.value.zoom },
+                        ..*keyframe
+                    }
+                })
+                .collect();
+            tracks.push(Track::camera(self.camera_node, view_center, camera_keyframes));
+        }
+        tracks.push(Track::audio(self.script.audio_cues.clone()));
+        Timeline::new(tracks)
+    }
+}
+
+impl Scene for CutsceneScene {
+    fn update(&mut self, game: &mut Game, delta_time: f32, input: &InputState) {
+        let layout = Layout::new(game.get_design_resolution());
+
+        if self.timeline.is_none() {
+            self.timeline = Some(self.build_timeline(game, &layout));
+        }
+
+        if !self.scene_graph.has_component(self.progress_bar_node) {
+            let progress_bar_plane = game.drawable_generator.generate_plane_from_image(
+                (0.0, 0.0, 0.0, 0.0),
+                -0.9,
+                (1.0, 1.0, 1.0, 0.8),
+                None,
+                ShaderSource::Name(DEFAULT_SHADER_NAME)
+            ).map_err(|error| eprintln!("{}", error)).ok();
+            self.scene_graph.set_component(self.progress_bar_node, progress_bar_plane.map(|plane| Box::new(plane) as _));
+        }
+
+        let Some(timeline) = &mut self.timeline else {
+            return;
+        };
+
+        // 點擊暫停或繼續，左右鍵前後拖曳，返回或略過直接跳到結尾
+        if input.is_pressed(Action::Advance) {
+            timeline.set_paused(!timeline.is_paused());
+        }
+        if input.is_pressed(Action::Left) {
+            timeline.seek(&mut self.scene_graph, timeline.get_current_time() - SEEK_SECONDS);
+        }
+        if input.is_pressed(Action::Right) {
+            timeline.seek(&mut self.scene_graph, timeline.get_current_time() + SEEK_SECONDS);
+        }
+        if input.is_pressed(Action::Back) || input.is_pressed(Action::Menu) || input.is_pressed(Action::Skip) {
+            timeline.skip(&mut self.scene_graph);
+        }
+        timeline.update(&mut self.scene_graph, &mut game.audio_manager, delta_time);
+
+        // 暫停時顯示目前的播放進度
+        let progress = timeline.get_current_time() / timeline.get_duration().max(f32::EPSILON);
+        if let Some(progress_bar_plane) = self.scene_graph.get_component_mut::<Plane>(self.progress_bar_node) {
+            progress_bar_plane.set_rect((0.0, 0.0, layout.width * progress, PROGRESS_BAR_HEIGHT * layout.scale));
+        }
+        self.scene_graph.set_visible(self.progress_bar_node, timeline.is_paused());
+
+        if timeline.is_finished() && !self.is_finished {
+            self.is_finished = true;
+            let game_play_scene = GamePlayScene::new(self.story_context.clone(), None);
+            game.change_scene(Rc::new(RefCell::new(game_play_scene)), SCENE_TRANSITION);
+        }
+    }
+
+    fn get_scene_graph(&self) -> Option<&SceneGraph> {
+        Some(&self.scene_graph)
+    }
+
+    fn get_scene_graph_mut(&mut self) -> Option<&mut SceneGraph> {
+        Some(&mut self.scene_graph)
+    }
+}
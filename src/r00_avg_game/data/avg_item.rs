@@ -4,6 +4,7 @@ pub struct AvgItem {
     pub center_character_image_path: Option<String>,
     pub character_name: Option<String>,
     pub content: Option<String>,
+    pub voice_path: Option<String>,
     pub selection_items: Option<Vec<SelectionItem>>,
     pub next_index: Option<u32>,
 }
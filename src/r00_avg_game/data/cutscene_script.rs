@@ -0,0 +1,135 @@
+use std::fs;
+use crate::engine::timeline::{AudioCue, AudioEvent, CameraShot, Keyframe};
+use crate::engine::tween::{Easing, TweenProperty};
+
+// 座標與字級都以 1920x1080 為準，播放時再依版面縮放
+pub enum CutsceneObject {
+    Sprite { name: String, image_path: String },
+    Text { name: String, left_bottom: (f32, f32), font_size: u16, content: String },
+}
+
+pub struct CutsceneKeyframe {
+    pub target: String,
+    pub keyframe: Keyframe<TweenProperty>,
+}
+
+pub struct CutsceneScript {
+    pub objects: Vec<CutsceneObject>,
+    pub keyframes: Vec<CutsceneKeyframe>,
+    pub camera_keyframes: Vec<Keyframe<CameraShot>>,
+    pub audio_cues: Vec<AudioCue>,
+}
+
+impl CutsceneScript {
+    // 每行一個指令，# 開頭為註解：
+    // sprite <名稱> <圖片路徑>
+    // text <名稱> <x> <y> <字級> <內容>
+    // key <秒> <名稱> alpha|position|scale|color|reveal <數值...> [緩動]
+    // camera <秒> <x> <y> <縮放> [緩動]
+    // music|sound|voice <秒> <路徑>
+    // 無法解析的行會被略過
+    pub fn load(path: &str) -> Self {
+        let mut script = Self {
+            objects: vec![],
+            keyframes: vec![],
+            camera_keyframes: vec![],
+            audio_cues: vec![],
+        };
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) => {
+                eprintln!("Can't read cutscene {}: {}", path, error);
+                return script;
+            }
+        };
+
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            let mut rest = line;
+            let is_parsed = match split_token(&mut rest) {
+                Some("sprite") => parse_sprite(rest).map(|object| script.objects.push(object)),
+                Some("text") => parse_text(rest).map(|object| script.objects.push(object)),
+                Some("key") => parse_keyframe(rest).map(|keyframe| script.keyframes.push(keyframe)),
+                Some("camera") => parse_camera_keyframe(rest).map(|keyframe| script.camera_keyframes.push(keyframe)),
+                Some(command @ ("music" | "sound" | "voice")) => parse_audio_cue(command, rest).map(|cue| script.audio_cues.push(cue)),
+                _ => None,
+            }.is_some();
+            if !is_parsed {
+                eprintln!("Invalid cutscene line: {}", line);
+            }
+        }
+        script
+    }
+}
+
+// 取出下一個以空白分隔的字，rest 會留下剩餘的部分
+fn split_token<'a>(rest: &mut &'a str) -> Option<&'a str> {
+    let trimmed = rest.trim_start();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let (token, remaining) = trimmed.split_once(char::is_whitespace).unwrap_or((trimmed, ""));
+    *rest = remaining;
+    Some(token)
+}
+
+fn parse_numbers(rest: &mut &str, count: usize) -> Option<Vec<f32>> {
+    (0..count).map(|_| split_token(rest)?.parse().ok()).collect()
+}
+
+// 沒寫緩動時為線性
+fn parse_easing(rest: &mut &str) -> Option<Easing> {
+    match split_token(rest) {
+        Some(name) => Easing::from_name(name),
+        None => Some(Easing::Linear),
+    }
+}
+
+fn parse_sprite(mut rest: &str) -> Option<CutsceneObject> {
+    let name = split_token(&mut rest)?.to_string();
+    let image_path = split_token(&mut rest)?.to_string();
+    Some(CutsceneObject::Sprite { name, image_path })
+}
+
+fn parse_text(mut rest: &str) -> Option<CutsceneObject> {
+    let name = split_token(&mut rest)?.to_string();
+    let left_bottom = parse_numbers(&mut rest, 2)?;
+    let font_size = split_token(&mut rest)?.parse().ok()?;
+    let content = rest.trim().to_string();
+    Some(CutsceneObject::Text { name, left_bottom: (left_bottom[0], left_bottom[1]), font_size, content })
+}
+
+fn parse_keyframe(mut rest: &str) -> Option<CutsceneKeyframe> {
+    let time = split_token(&mut rest)?.parse().ok()?;
+    let target = split_token(&mut rest)?.to_string();
+    let value = match split_token(&mut rest)? {
+        "alpha" => TweenProperty::Alpha(parse_numbers(&mut rest, 1)?[0]),
+        "position" => parse_numbers(&mut rest, 2).map(|values| TweenProperty::Position((values[0], values[1])))?,
+        "scale" => parse_numbers(&mut rest, 2).map(|values| TweenProperty::Scale((values[0], values[1])))?,
+        "color" => parse_numbers(&mut rest, 4).map(|values| TweenProperty::Color((values[0], values[1], values[2], values[3])))?,
+        "reveal" => TweenProperty::TextReveal(parse_numbers(&mut rest, 1)?[0]),
+        _ => return None,
+    };
+    let easing = parse_easing(&mut rest)?;
+    Some(CutsceneKeyframe { target, keyframe: Keyframe { time, value, easing } })
+}
+
+fn parse_camera_keyframe(mut rest: &str) -> Option<Keyframe<CameraShot>> {
+    let values = parse_numbers(&mut rest, 4)?;
+    let easing = parse_easing(&mut rest)?;
+    Some(Keyframe {
+        time: values[0],
+        value: CameraShot { position: (values[1], values[2]), zoom: values[3] },
+        easing,
+    })
+}
+
+fn parse_audio_cue(command: &str, mut rest: &str) -> Option<AudioCue> {
+    let time = split_token(&mut rest)?.parse().ok()?;
+    let file_path = split_token(&mut rest)?.to_string();
+    let event = match command {
+        "music" => AudioEvent::Music(file_path),
+        "sound" => AudioEvent::Sound(file_path),
+        _ => AudioEvent::Voice(file_path),
+    };
+    Some(AudioCue { time, event })
+}
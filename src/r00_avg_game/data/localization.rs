@@ -0,0 +1,89 @@
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Language {
+    TraditionalChinese,
+    English,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TextId {
+    NewGame,
+    Continue,
+    Load,
+    Save,
+    Settings,
+    Resume,
+    Quit,
+    Back,
+    SaveSlot,
+    EmptySlot,
+    BgmVolume,
+    SeVolume,
+    VoiceVolume,
+    TextSpeed,
+    AutoDelay,
+    AutoDelayPerCharacter,
+    WindowOpacity,
+    Fullscreen,
+    SkipUnread,
+    Language,
+    On,
+    Off,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::TraditionalChinese, Language::English];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Language::ALL.into_iter().find(|language| language.get_name() == name)
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Language::TraditionalChinese => "zh-TW",
+            Language::English => "en",
+        }
+    }
+
+    pub fn get_display_name(&self) -> &'static str {
+        match self {
+            Language::TraditionalChinese => "繁體中文",
+            Language::English => "English",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Language::ALL.iter().position(|language| language == self).unwrap_or(0);
+        Language::ALL[(index + 1) % Language::ALL.len()]
+    }
+
+    pub fn get_text(&self, text_id: TextId) -> &'static str {
+        let (traditional_chinese, english) = match text_id {
+            TextId::NewGame => ("開始遊戲", "New Game"),
+            TextId::Continue => ("繼續遊戲", "Continue"),
+            TextId::Load => ("讀取遊戲", "Load"),
+            TextId::Save => ("儲存遊戲", "Save"),
+            TextId::Settings => ("設定", "Settings"),
+            TextId::Resume => ("回到遊戲", "Resume"),
+            TextId::Quit => ("結束遊戲", "Quit"),
+            TextId::Back => ("返回", "Back"),
+            TextId::SaveSlot => ("存檔", "Slot"),
+            TextId::EmptySlot => ("（空）", "(Empty)"),
+            TextId::BgmVolume => ("音樂音量", "BGM Volume"),
+            TextId::SeVolume => ("音效音量", "SE Volume"),
+            TextId::VoiceVolume => ("語音音量", "Voice Volume"),
+            TextId::TextSpeed => ("文字速度", "Text Speed"),
+            TextId::AutoDelay => ("自動等待", "Auto Delay"),
+            TextId::AutoDelayPerCharacter => ("每字等待", "Delay per Character"),
+            TextId::WindowOpacity => ("對話框透明度", "Window Opacity"),
+            TextId::Fullscreen => ("全螢幕", "Fullscreen"),
+            TextId::SkipUnread => ("略過未讀", "Skip Unread"),
+            TextId::Language => ("語言", "Language"),
+            TextId::On => ("開", "On"),
+            TextId::Off => ("關", "Off"),
+        };
+        match self {
+            Language::TraditionalChinese => traditional_chinese,
+            Language::English => english,
+        }
+    }
+}
@@ -1 +1,7 @@
-pub(crate) mod avg_item;
\ No newline at end of file
+pub(crate) mod avg_item;
+pub(crate) mod settings;
+pub(crate) mod read_history;
+pub(crate) mod save_data;
+pub(crate) mod story_context;
+pub(crate) mod localization;
+pub(crate) mod cutscene_script;
\ No newline at end of file
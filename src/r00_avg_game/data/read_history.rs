@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+// 已讀紀錄跨存檔共用，重新開啟遊戲後「只略過已讀」仍然有效
+pub const READ_HISTORY_PATH: &str = "./saves/read_history.sav";
+
+pub struct ReadHistory {
+    read_indices: HashSet<usize>,
+}
+
+impl ReadHistory {
+    pub fn new() -> Self {
+        Self {
+            read_indices: HashSet::new(),
+        }
+    }
+
+    // 檔案不存在時視為全部未讀，無法解析的索引會被略過
+    pub fn load(path: &str) -> Self {
+        let mut read_history = Self::new();
+        let Ok(text) = fs::read_to_string(path) else {
+            return read_history;
+        };
+
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            if key.trim() == "read" {
+                read_history.read_indices.extend(value
+                    .split(',')
+                    .filter_map(|index| index.trim().parse::<usize>().ok()));
+            }
+        }
+        read_history
+    }
+
+    pub fn save(&self, path: &str) {
+        let mut read_indices = self.read_indices.iter().collect::<Vec<_>>();
+        read_indices.sort();
+        let read = read_indices
+            .iter()
+            .map(|index| index.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let result = match Path::new(path).parent() {
+            Some(directory) => fs::create_dir_all(directory),
+            None => Ok(()),
+        }.and_then(|_| fs::write(path, format!("read = {}\n", read)));
+        if let Err(error) = result {
+            eprintln!("Can't write read history to {}: {}", path, error);
+        }
+    }
+
+    // 回傳 true 表示這一句是第一次讀到
+    pub fn mark_read(&mut self, index: usize) -> bool {
+        self.read_indices.insert(index)
+    }
+
+    pub fn is_read(&self, index: usize) -> bool {
+        self.read_indices.contains(&index)
+    }
+}
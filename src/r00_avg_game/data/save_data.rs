@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SAVE_DIRECTORY: &str = "./saves";
+pub const SAVE_SLOT_COUNT: usize = 3;
+
+#[derive(Clone)]
+pub struct SaveData {
+    pub current_index: usize,
+    pub backlog_indices: Vec<usize>,
+    pub saved_at: u64,
+}
+
+impl SaveData {
+    pub fn new(current_index: usize, backlog_indices: Vec<usize>) -> Self {
+        Self {
+            current_index,
+            backlog_indices,
+            saved_at: get_current_timestamp(),
+        }
+    }
+
+    // 索引超出目前劇本範圍的存檔（舊版劇本或被修改過）視為空的欄位
+    pub fn load(slot: usize, avg_item_count: usize) -> Option<Self> {
+        let text = fs::read_to_string(get_slot_path(slot)).ok()?;
+        let save_data = Self::parse(&text).filter(|save_data| save_data.is_valid(avg_item_count));
+        if save_data.is_none() {
+            eprintln!("Invalid save data in slot {}", slot);
+        }
+        save_data
+    }
+
+    // 回傳最後儲存的存檔，用於「繼續遊戲」
+    pub fn load_latest(avg_item_count: usize) -> Option<Self> {
+        (0..SAVE_SLOT_COUNT)
+            .filter_map(|slot| Self::load(slot, avg_item_count))
+            .max_by_key(|save_data| save_data.saved_at)
+    }
+
+    pub fn save(&mut self, slot: usize) {
+        self.saved_at = get_current_timestamp();
+        if let Err(error) = fs::create_dir_all(SAVE_DIRECTORY)
+            .and_then(|_| fs::write(get_slot_path(slot), self.to_text())) {
+            eprintln!("Can't write save slot {}: {}", slot, error);
+        }
+    }
+
+    fn is_valid(&self, avg_item_count: usize) -> bool {
+        self.current_index < avg_item_count &&
+            self.backlog_indices.iter().all(|index| *index < avg_item_count)
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let mut current_index = None;
+        let mut backlog_indices = Vec::new();
+        let mut saved_at = 0;
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "current_index" => current_index = value.parse().ok(),
+                "backlog" => backlog_indices = value
+                    .split(',')
+                    .filter_map(|index| index.trim().parse().ok())
+                    .collect(),
+                "saved_at" => saved_at = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            current_index: current_index?,
+            backlog_indices,
+            saved_at,
+        })
+    }
+
+    fn to_text(&self) -> String {
+        let backlog = self.backlog_indices
+            .iter()
+            .map(|index| index.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("current_index = {}\nbacklog = {}\nsaved_at = {}\n", self.current_index, backlog, self.saved_at)
+    }
+}
+
+fn get_current_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs())
+}
+
+fn get_slot_path(slot: usize) -> String {
+    Path::new(SAVE_DIRECTORY).join(format!("slot{}.sav", slot + 1)).to_string_lossy().to_string()
+}
@@ -0,0 +1,98 @@
+use std::fs;
+use crate::r00_avg_game::data::localization::Language;
+
+pub const SETTINGS_PATH: &str = "./settings.cfg";
+pub const RATIO_RANGE: (f32, f32) = (0.0, 1.0);
+pub const TEXT_SPEED_RANGE: (f32, f32) = (5.0, 60.0);
+pub const AUTO_DELAY_RANGE: (f32, f32) = (0.5, 5.0);
+pub const AUTO_DELAY_PER_CHARACTER_RANGE: (f32, f32) = (0.0, 0.5);
+
+pub struct Settings {
+    pub is_skip_all: bool,
+    pub auto_base_delay_seconds: f32,
+    pub auto_delay_per_character_seconds: f32,
+    pub bgm_volume: f32,
+    pub se_volume: f32,
+    pub voice_volume: f32,
+    pub text_speed_per_second: f32,
+    pub text_window_opacity: f32,
+    pub is_fullscreen: bool,
+    pub language: Language,
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        Self {
+            is_skip_all: false,
+            auto_base_delay_seconds: 1.0,
+            auto_delay_per_character_seconds: 0.08,
+            bgm_volume: 0.5,
+            se_volume: 0.5,
+            voice_volume: 0.5,
+            text_speed_per_second: 10.0,
+            text_window_opacity: 1.0,
+            is_fullscreen: false,
+            language: Language::TraditionalChinese,
+        }
+    }
+
+    // 設定檔不存在或某些欄位無法解析時使用預設值
+    pub fn load(path: &str) -> Self {
+        let mut settings = Self::new();
+        let Ok(text) = fs::read_to_string(path) else {
+            return settings;
+        };
+
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "is_skip_all" => settings.is_skip_all = value.parse().unwrap_or(settings.is_skip_all),
+                "auto_base_delay_seconds" => settings.auto_base_delay_seconds = value.parse().unwrap_or(settings.auto_base_delay_seconds),
+                "auto_delay_per_character_seconds" => settings.auto_delay_per_character_seconds = value.parse().unwrap_or(settings.auto_delay_per_character_seconds),
+                "bgm_volume" => settings.bgm_volume = value.parse().unwrap_or(settings.bgm_volume),
+                "se_volume" => settings.se_volume = value.parse().unwrap_or(settings.se_volume),
+                "voice_volume" => settings.voice_volume = value.parse().unwrap_or(settings.voice_volume),
+                "text_speed_per_second" => settings.text_speed_per_second = value.parse().unwrap_or(settings.text_speed_per_second),
+                "text_window_opacity" => settings.text_window_opacity = value.parse().unwrap_or(settings.text_window_opacity),
+                "is_fullscreen" => settings.is_fullscreen = value.parse().unwrap_or(settings.is_fullscreen),
+                "language" => settings.language = Language::from_name(value).unwrap_or(settings.language),
+                _ => eprintln!("Unknown setting: {}", line),
+            }
+        }
+        settings.clamp_values();
+        settings
+    }
+
+    // 手動修改過的設定檔可能超出設定畫面能調整的範圍
+    fn clamp_values(&mut self) {
+        let clamp = |value: f32, range: (f32, f32)| if value.is_nan() { range.0 } else { value.clamp(range.0, range.1) };
+        self.auto_base_delay_seconds = clamp(self.auto_base_delay_seconds, AUTO_DELAY_RANGE);
+        self.auto_delay_per_character_seconds = clamp(self.auto_delay_per_character_seconds, AUTO_DELAY_PER_CHARACTER_RANGE);
+        self.bgm_volume = clamp(self.bgm_volume, RATIO_RANGE);
+        self.se_volume = clamp(self.se_volume, RATIO_RANGE);
+        self.voice_volume = clamp(self.voice_volume, RATIO_RANGE);
+        self.text_speed_per_second = clamp(self.text_speed_per_second, TEXT_SPEED_RANGE);
+        self.text_window_opacity = clamp(self.text_window_opacity, RATIO_RANGE);
+    }
+
+    pub fn save(&self, path: &str) {
+        let text = format!(
+            "is_skip_all = {}\nauto_base_delay_seconds = {}\nauto_delay_per_character_seconds = {}\nbgm_volume = {}\nse_volume = {}\nvoice_volume = {}\ntext_speed_per_second = {}\ntext_window_opacity = {}\nis_fullscreen = {}\nlanguage = {}\n",
+            self.is_skip_all,
+            self.auto_base_delay_seconds,
+            self.auto_delay_per_character_seconds,
+            self.bgm_volume,
+            self.se_volume,
+            self.voice_volume,
+            self.text_speed_per_second,
+            self.text_window_opacity,
+            self.is_fullscreen,
+            self.language.get_name());
+        if let Err(error) = fs::write(path, text) {
+            eprintln!("Can't write settings to {}: {}", path, error);
+        }
+    }
+}
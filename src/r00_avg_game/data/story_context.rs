@@ -0,0 +1,13 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::r00_avg_game::data::avg_item::AvgItem;
+use crate::r00_avg_game::data::read_history::ReadHistory;
+use crate::r00_avg_game::data::settings::Settings;
+
+// 劇本與跨場景共用的資料，標題、讀檔與遊戲場景之間互相傳遞
+#[derive(Clone)]
+pub struct StoryContext {
+    pub avg_items: Rc<Vec<AvgItem>>,
+    pub settings: Rc<RefCell<Settings>>,
+    pub read_history: Rc<RefCell<ReadHistory>>,
+}
@@ -1,13 +1,28 @@
-use std::cmp::{max, PartialEq};
+use std::cell::RefCell;
+use std::cmp::PartialEq;
+use std::rc::Rc;
 use std::string::ToString;
-use nalgebra_glm::Mat4;
-use sdl2::mouse::SystemCursor::No;
-use crate::engine::drawable_component::DrawableComponent;
+use sdl2::mouse::SystemCursor;
+use crate::engine::drawable_implements::generator::ShaderSource;
 use crate::engine::drawable_implements::plane::Plane;
 use crate::engine::drawable_implements::text::Text;
-use crate::engine::game::Game;
+use crate::engine::game::{Game, DEFAULT_SHADER_NAME};
+use crate::engine::input::action::Action;
+use crate::engine::input::binding::InputDevice;
+use crate::engine::input::input_state::InputState;
 use crate::engine::scene::Scene;
+use crate::engine::scene_graph::{NodeId, SceneGraph};
+use crate::engine::transition::Transition;
+use crate::engine::tween::{Animation, AnimationId, Easing, TweenPlayer, TweenProperty};
+use crate::r00_avg_game::backlog::BacklogScene;
+use crate::r00_avg_game::common::{get_hovered_index, highlight_texts, CONFIRM_SOUND_FILE_PATH, FONT_PATH, HOVER_SOUND_FILE_PATH, OVERLAY_OPEN_TRANSITION, TOUCH_TARGET_PADDING};
 use crate::r00_avg_game::data::avg_item::AvgItem;
+use crate::r00_avg_game::data::read_history::{ReadHistory, READ_HISTORY_PATH};
+use crate::r00_avg_game::data::save_data::SaveData;
+use crate::r00_avg_game::data::settings::{Settings, TEXT_SPEED_RANGE};
+use crate::r00_avg_game::data::story_context::StoryContext;
+use crate::r00_avg_game::layout::Layout;
+use crate::r00_avg_game::pause_menu::PauseMenuScene;
 
 const NO_IMAGE : &str = "NO_IMAGE";
 
@@ -24,257 +39,537 @@ enum Status {
 pub struct GamePlayScene {
     current_index: isize,
     next_index: usize,
-    avg_items: Vec<AvgItem>,
+    avg_items: Rc<Vec<AvgItem>>,
     status: Status,
-    background_plane: Option<Plane>,
-    center_character_plane: Option<Plane>,
-    character_name_plane: Option<Text>,
-    content_plane: Option<Text>,
-    selections_texts: Option<Vec<Text>>,
-
-    // static
-    frame_plane: Option<Plane>,
-    no_name_frame_plane: Option<Plane>,
-    selection_background_plane: Option<Plane>,
+    settings: Rc<RefCell<Settings>>,
+    read_history: Rc<RefCell<ReadHistory>>,
+    is_skip_toggled: bool,
+    is_auto_mode: bool,
+    focused_selection_index: Option<usize>,
+    is_ui_hidden: bool,
+    backlog_indices: Vec<usize>,
+    wait_time: f32,
+    voice_remaining_time: f32,
+    // 進入等待時語音還剩的秒數，自動模式以此計算要等多久
+    wait_voice_seconds: f32,
+    tween_player: TweenPlayer,
+    // 進場與退場的動畫，播完才切換到下一個狀態
+    status_animation_id: Option<AnimationId>,
+
+    scene_graph: SceneGraph,
+    background_node: NodeId,
+    center_character_node: NodeId,
+    ui_node: NodeId,
+    frame_node: NodeId,
+    no_name_frame_node: NodeId,
+    character_name_node: NodeId,
+    content_node: NodeId,
+    selection_node: NodeId,
+    selection_background_node: NodeId,
+    selection_texts_node: NodeId,
+    auto_indicator_node: NodeId,
 
     // debug
-    debug_information_plane: Option<Text>,
+    debug_information_node: NodeId,
 }
 
 impl GamePlayScene {
-    pub(crate) fn new(avg_items: Vec<AvgItem>) -> Self {
+    pub(crate) fn new(story_context: StoryContext, save_data: Option<SaveData>) -> Self {
+        let StoryContext { avg_items, settings, read_history } = story_context;
+        let (next_index, backlog_indices) = save_data
+            .map_or((0, Vec::new()), |save_data| (save_data.current_index, save_data.backlog_indices));
+
+        // 背景與立繪在下，介面在上；選項整組跟著選項狀態顯示或隱藏
+        let mut scene_graph = SceneGraph::new();
+        let root = scene_graph.get_root();
+        let background_node = scene_graph.add_node(root, None);
+        let center_character_node = scene_graph.add_node(root, None);
+        let ui_node = scene_graph.add_node(root, None);
+        let frame_node = scene_graph.add_node(ui_node, None);
+        let no_name_frame_node = scene_graph.add_node(ui_node, None);
+        let character_name_node = scene_graph.add_node(ui_node, None);
+        let content_node = scene_graph.add_node(ui_node, None);
+        let selection_node = scene_graph.add_node(ui_node, None);
+        let selection_background_node = scene_graph.add_node(selection_node, None);
+        let selection_texts_node = scene_graph.add_node(selection_node, None);
+        let auto_indicator_node = scene_graph.add_node(ui_node, None);
+        let debug_information_node = scene_graph.add_node(root, None);
+        // 各層的前後由節點的 z_order 決定，元件的 z 只在層內排序
+        for (node_id, z_order) in [
+            (center_character_node, -0.1),
+            (ui_node, -0.2),
+            (selection_node, -0.2),
+            (debug_information_node, -0.3),
+        ] {
+            if let Some(node) = scene_graph.get_node_mut(node_id) {
+                node.z_order = z_order;
+            }
+        }
         Self {
             current_index: -1,
-            next_index: 0,
+            next_index,
             avg_items,
             status: Status::LoadNext,
-            background_plane: None,
-            center_character_plane: None,
-            character_name_plane: None,
-            content_plane: None,
-            selection_background_plane: None,
-            selections_texts: None,
-            frame_plane: None,
-            no_name_frame_plane: None,
-            debug_information_plane: None,
+            settings,
+            read_history,
+            is_skip_toggled: false,
+            is_auto_mode: false,
+            focused_selection_index: None,
+            is_ui_hidden: false,
+            backlog_indices,
+            wait_time: 0.0,
+            voice_remaining_time: 0.0,
+            wait_voice_seconds: 0.0,
+            tween_player: TweenPlayer::new(),
+            status_animation_id: None,
+            scene_graph,
+            background_node,
+            center_character_node,
+            ui_node,
+            frame_node,
+            no_name_frame_node,
+            character_name_node,
+            content_node,
+            selection_node,
+            selection_background_node,
+            selection_texts_node,
+            auto_indicator_node,
+            debug_information_node,
+        }
+    }
+
+    fn can_skip_current(&self) -> bool {
+        self.current_index >= 0 &&
+            (self.settings.borrow().is_skip_all || self.read_history.borrow().is_read(self.current_index as usize))
+    }
+
+    fn get_auto_delay(&self) -> f32 {
+        let settings = self.settings.borrow();
+        let content_length = self.scene_graph
+            .get_component::<Text>(self.content_node)
+            .map_or(0, |content_text| content_text.get_content_char_indices_count());
+        let reading_delay = settings.auto_base_delay_seconds +
+            settings.auto_delay_per_character_seconds * content_length as f32;
+        reading_delay.max(self.wait_voice_seconds + AUTO_VOICE_PADDING_SECONDS)
+    }
+
+    // 依目前狀態決定場景圖中哪些節點要顯示
+    fn update_visibility(&mut self) {
+        let has_content = self.scene_graph.has_component(self.content_node);
+        let has_character_name = self.scene_graph.has_component(self.character_name_node);
+        self.scene_graph.set_visible(self.ui_node, !self.is_ui_hidden);
+        self.scene_graph.set_visible(self.frame_node, has_content && has_character_name);
+        self.scene_graph.set_visible(self.no_name_frame_node, has_content && !has_character_name);
+        self.scene_graph.set_visible(self.selection_node, self.status == Status::SelectionWait || self.status == Status::EndCurrent);
+        self.scene_graph.set_visible(self.auto_indicator_node, self.is_auto_mode);
+    }
+
+    fn is_status_animation_playing(&self) -> bool {
+        self.status_animation_id.is_some_and(|animation_id| self.tween_player.is_playing(animation_id))
+    }
+
+    fn complete_status_animation(&mut self) {
+        if let Some(animation_id) = self.status_animation_id {
+            self.tween_player.complete(&mut self.scene_graph, animation_id);
+        }
+    }
+
+    // 淡出下一句會換掉的內容，選中的選項先放大一下再淡出；全部播完才算讀過這一句
+    fn end_current(&mut self, selected_index: Option<usize>) {
+        let empty_string = EMPTY_STRING.to_string();
+        let avg_item = &self.avg_items[self.current_index as usize];
+        let next_avg_item = self.avg_items.get(self.next_index);
+
+        let is_change_background = next_avg_item
+            .and_then(|avg_item| avg_item.background_image_path.as_ref())
+            .is_some();
+        let is_change_left_character = next_avg_item
+            .and_then(|avg_item| avg_item.center_character_image_path.as_ref())
+            .is_some();
+        let current_character_name = avg_item
+            .character_name
+            .as_ref()
+            .unwrap_or(&empty_string)
+            .as_str();
+        let next_character_name = next_avg_item
+            .and_then(|avg_item| avg_item.character_name.as_ref())
+            .unwrap_or(&empty_string)
+            .as_str();
+        let is_change_character_name = current_character_name != next_character_name;
+
+        let mut animations = vec![];
+        if is_change_background {
+            animations.push(fade_out(self.background_node));
+        }
+        if is_change_left_character {
+            animations.push(fade_out(self.center_character_node));
         }
+        if is_change_character_name {
+            animations.push(fade_out(self.character_name_node));
+        }
+        animations.push(fade_out(self.content_node));
+        animations.push(fade_out(self.selection_background_node));
+        for (index, selection_text_node) in self.scene_graph.get_children(self.selection_texts_node).into_iter().enumerate() {
+            if Some(index) == selected_index {
+                animations.push(Animation::sequence(vec![
+                    Animation::tween(selection_text_node, TweenProperty::Scale((SELECTED_SCALE, SELECTED_SCALE)), SELECTED_POP_SECONDS, Easing::BackOut),
+                    fade_out(selection_text_node),
+                ]));
+            } else {
+                animations.push(fade_out(selection_text_node));
+            }
+        }
+
+        let read_history = Rc::clone(&self.read_history);
+        let current_index = self.current_index as usize;
+        self.status_animation_id = Some(self.tween_player.play(Animation::sequence(vec![
+            Animation::parallel(animations),
+            Animation::callback(move |_| {
+                let mut read_history = read_history.borrow_mut();
+                if read_history.mark_read(current_index) {
+                    read_history.save(READ_HISTORY_PATH);
+                }
+            }),
+        ])));
+        self.status = Status::EndCurrent;
+    }
+
+    fn get_story_context(&self) -> StoryContext {
+        StoryContext {
+            avg_items: Rc::clone(&self.avg_items),
+            settings: Rc::clone(&self.settings),
+            read_history: Rc::clone(&self.read_history),
+        }
+    }
+
+    // 存檔的回顧紀錄不含目前這一句，讀檔後載入時會再加回來
+    fn get_save_data(&self) -> Option<SaveData> {
+        if self.current_index < 0 {
+            return None;
+        }
+
+        let current_index = self.current_index as usize;
+        let mut backlog_indices = self.backlog_indices.clone();
+        if backlog_indices.last() == Some(&current_index) {
+            backlog_indices.pop();
+        }
+        Some(SaveData::new(current_index, backlog_indices))
+    }
+
+    // 讀檔後第一次載入時，往回找最近一次設定的背景、立繪與音樂
+    fn find_latest_in_history(&self, get_value: impl Fn(&AvgItem) -> Option<&String>) -> Option<String> {
+        std::iter::once(&self.next_index)
+            .chain(self.backlog_indices.iter().rev())
+            .filter_map(|index| self.avg_items.get(*index))
+            .find_map(get_value)
+            .cloned()
+    }
+
+    fn get_backlog_lines(&self) -> Vec<String> {
+        self.backlog_indices
+            .iter()
+            .map(|avg_item_index| {
+                let avg_item = &self.avg_items[*avg_item_index];
+                let content = avg_item.content.clone().unwrap_or_default();
+                match &avg_item.character_name {
+                    Some(character_name) => format!("{}「{}」", character_name, content),
+                    None => content,
+                }
+            })
+            .collect()
     }
 }
 
-const VERTEX_SHADER: &str = include_str!("../shaders/vertex_shader.glsl");
-const FRAGMENT_SHADER: &str = include_str!("../shaders/fragment_shader.glsl");
-const FONT_PATH: &str = "./resources/fonts/SourceHanSerifTC-Heavy.otf";
-const FADE_SPEED_PER_SECOND: f32 = 2.0;
-const IMMEDIATELY_FADE_SPEED : f32 = 10000.0;
-const INPUT_SPEED_PER_SECOND: f32 = 10.0;
-const IMMEDIATELY_INPUT_SPEED : f32 = 10000.0;
+fn fade_in(node_id: NodeId) -> Animation {
+    Animation::tween(node_id, TweenProperty::Alpha(1.0), FADE_SECONDS, Easing::Linear)
+}
+
+fn fade_out(node_id: NodeId) -> Animation {
+    Animation::tween(node_id, TweenProperty::Alpha(0.0), FADE_SECONDS, Easing::Linear)
+}
+
+const FADE_SECONDS: f32 = 0.5;
+const SKIP_PLAYBACK_SPEED: f32 = 10.0;
+const SELECTION_FADE_SECONDS: f32 = 0.2;
+const SELECTION_BACKGROUND_ALPHA: f32 = 0.75;
+const SELECTED_SCALE: f32 = 1.1;
+const SELECTED_POP_SECONDS: f32 = 0.15;
+const SKIP_WAIT_SECONDS: f32 = 0.05;
+const AUTO_VOICE_PADDING_SECONDS: f32 = 0.5;
+const CHARACTER_ENTER_OFFSET: f32 = 40.0;
 const EMPTY_STRING: &str = "";
-const CONFIRM_SOUND_FILE_PATH: &str = "resources/musics/confirm.mp3";
 
 
 impl Scene for GamePlayScene {
-    fn update(&mut self, game: &mut Game, delta_time: f32, hit_position: Option<(i32, i32)>) {
+    fn update(&mut self, game: &mut Game, delta_time: f32, input: &InputState) {
         let empty_string = EMPTY_STRING.to_string();
+        let layout = Layout::new(game.get_design_resolution());
+        let hit_position = input.hit_position.filter(|_| input.is_pressed(Action::Advance));
 
         #[cfg(debug_assertions)] {
-            self.debug_information_plane = Some(game.drawable_generator.generate_text(
+            let asset_manager = game.drawable_generator.asset_manager.borrow();
+            let texture_count = asset_manager.get_texture_usages().len();
+            let texture_memory_usage = asset_manager.get_texture_memory_usage();
+            drop(asset_manager);
+            let debug_information_text = game.drawable_generator.generate_text(
                 (0.0, 0.0),
-                -0.3,
-                &format!("{}, {}",
+                0.0,
+                &format!("{}, {:.2}, {} textures {} KB, {} draw calls, {}",
                          delta_time,
+                         game.get_interpolation_alpha(),
+                         texture_count,
+                         texture_memory_usage / 1024,
+                         game.sprite_batch.get_draw_call_count(),
                          hit_position
                              .map_or_else(|| EMPTY_STRING.to_string(), |hit_position| format!("({}, {})", hit_position.0, hit_position.1))),
                 1.0,
                 (1.0, 1.0, 0.0, 1.0),
                 FONT_PATH,
                 24,
-                VERTEX_SHADER,
-                FRAGMENT_SHADER
-            ));
+                ShaderSource::Name(DEFAULT_SHADER_NAME)
+            ).map_err(|error| eprintln!("{}", error)).ok();
+            self.scene_graph.set_component(self.debug_information_node, debug_information_text.map(|text| Box::new(text) as _));
         }
 
-        if self.frame_plane.is_none() {
-            self.frame_plane = Some(game.drawable_generator.generate_plane_from_image(
-                (0.0, 0.0, 1920.0, 1080.0),
-                -0.2,
+        if !self.scene_graph.has_component(self.frame_node) {
+            let frame_plane = game.drawable_generator.generate_plane_from_image(
+                layout.get_bottom_rect(),
+                0.0,
                 (1.0, 1.0, 1.0, 1.0),
                 Some(&"resources/images/frame.png".to_string()),
-                VERTEX_SHADER,
-                FRAGMENT_SHADER
-            ))
+                ShaderSource::Name(DEFAULT_SHADER_NAME)
+            ).map_err(|error| eprintln!("{}", error)).ok();
+            self.scene_graph.set_component(self.frame_node, frame_plane.map(|plane| Box::new(plane) as _));
         }
 
-        if self.no_name_frame_plane.is_none() {
-            self.no_name_frame_plane = Some(game.drawable_generator.generate_plane_from_image(
-                (0.0, 0.0, 1920.0, 1080.0),
-                -0.2,
+        if !self.scene_graph.has_component(self.no_name_frame_node) {
+            let no_name_frame_plane = game.drawable_generator.generate_plane_from_image(
+                layout.get_bottom_rect(),
+                0.0,
                 (1.0, 1.0, 1.0, 1.0),
                 Some(&"resources/images/frame_no_name.png".to_string()),
-                VERTEX_SHADER,
-                FRAGMENT_SHADER
-            ))
+                ShaderSource::Name(DEFAULT_SHADER_NAME)
+            ).map_err(|error| eprintln!("{}", error)).ok();
+            self.scene_graph.set_component(self.no_name_frame_node, no_name_frame_plane.map(|plane| Box::new(plane) as _));
+        }
+
+        // 設定畫面可能在遊戲中途修改對話框透明度
+        let text_window_opacity = self.settings.borrow().text_window_opacity;
+        for frame_node in [self.frame_node, self.no_name_frame_node] {
+            if let Some(frame_plane) = self.scene_graph.get_component_mut::<Plane>(frame_node) {
+                frame_plane.set_alpha(text_window_opacity);
+            }
         }
 
-        if self.selection_background_plane.is_none() {
-            self.selection_background_plane = Some(game.drawable_generator.generate_plane_from_image(
-                (0.0, 0.0, 1920.0, 1080.0),
-                -0.4,
+        if !self.scene_graph.has_component(self.auto_indicator_node) {
+            let auto_indicator_text = game.drawable_generator.generate_text(
+                (layout.width - 160.0 * layout.scale, layout.height - 80.0 * layout.scale),
+                -0.1,
+                &"AUTO".to_string(),
+                1.0,
+                (1.0, 1.0, 1.0, 1.0),
+                FONT_PATH,
+                layout.scale_font_size(48),
+                ShaderSource::Name(DEFAULT_SHADER_NAME)
+            ).map_err(|error| eprintln!("{}", error)).ok();
+            self.scene_graph.set_component(self.auto_indicator_node, auto_indicator_text.map(|text| Box::new(text) as _));
+        }
+
+        if !self.scene_graph.has_component(self.selection_background_node) {
+            let selection_background_plane = game.drawable_generator.generate_plane_from_image(
+                layout.get_full_screen_rect(),
+                0.0,
                 (0.0, 0.0, 0.0, 0.0),
                 None,
-                VERTEX_SHADER,
-                FRAGMENT_SHADER
-            ))
+                ShaderSource::Name(DEFAULT_SHADER_NAME)
+            ).map_err(|error| eprintln!("{}", error)).ok();
+            self.scene_graph.set_component(self.selection_background_node, selection_background_plane.map(|plane| Box::new(plane) as _));
         }
 
-        let status = self.status;
-        match status {
-            Status::EnterCurrent => {
-                let fade_speed_per_second = if hit_position.is_some() { IMMEDIATELY_FADE_SPEED } else { FADE_SPEED_PER_SECOND };
-                let input_speed_per_second = if hit_position.is_some() { IMMEDIATELY_INPUT_SPEED } else { INPUT_SPEED_PER_SECOND };
+        self.update_visibility();
 
-                if let Some(background_plane) = &mut self.background_plane {
-                    background_plane.set_alpha(
-                        (background_plane.drawable.material.color[3] + delta_time * fade_speed_per_second).min(1.0));
-                }
+        // 隱藏介面或開啟選單的這幀語音仍在播放，倒數要放在提早返回之前
+        self.voice_remaining_time = (self.voice_remaining_time - delta_time).max(0.0);
 
-                if let Some(center_character_plane) = &mut self.center_character_plane {
-                    center_character_plane.set_alpha(
-                        (center_character_plane.drawable.material.color[3] + delta_time * fade_speed_per_second).min(1.0));
-                }
+        if self.is_ui_hidden {
+            // 觸控長按隱藏介面時，手指放開就恢復
+            let is_long_press_released = input.last_device == InputDevice::Touch && input.is_released(Action::HideUi);
+            if input.is_pressed(Action::Advance) || input.is_pressed(Action::Back) || input.is_pressed(Action::HideUi) || is_long_press_released {
+                self.is_ui_hidden = false;
+            }
+            return;
+        }
 
-                if let Some(character_name_text) = &mut self.character_name_plane {
-                    character_name_text.set_alpha(
-                        (character_name_text.drawable.material.color[3] + delta_time * fade_speed_per_second).min(1.0));
-                }
+        if input.is_pressed(Action::Menu) {
+            let pause_menu_scene = PauseMenuScene::new(self.get_story_context(), self.get_save_data());
+            game.push_scene(Rc::new(RefCell::new(pause_menu_scene)), Transition::None);
+            return;
+        }
 
-                if let Some(content_text) = &mut self.content_plane {
-                    content_text.set_alpha(1.0);
-                    content_text.set_range(
-                        &game.sdl2_ttf_context,
-                        (content_text.end_range_ratio + delta_time * input_speed_per_second / content_text.get_content_char_indices_count() as f32).min(1.0));
-                }
+        if input.is_pressed(Action::HideUi) {
+            self.is_ui_hidden = true;
+            return;
+        }
+
+        if input.is_pressed(Action::Backlog) && !self.backlog_indices.is_empty() {
+            game.push_scene(Rc::new(RefCell::new(BacklogScene::new(self.get_backlog_lines()))), OVERLAY_OPEN_TRANSITION);
+            return;
+        }
+
+        let mut is_advance_pressed = input.is_pressed(Action::Advance);
+        if input.is_pressed(Action::SkipToggle) {
+            self.is_skip_toggled = !self.is_skip_toggled;
+        }
+        if self.is_skip_toggled && is_advance_pressed {
+            self.is_skip_toggled = false;
+        }
+        if input.is_pressed(Action::AutoToggle) {
+            self.is_auto_mode = !self.is_auto_mode;
+        }
+        // 自動模式下任何點擊只用來取消自動模式
+        let hit_position = if self.is_auto_mode && is_advance_pressed {
+            self.is_auto_mode = false;
+            is_advance_pressed = false;
+            None
+        } else {
+            hit_position
+        };
+        if self.is_skip_toggled && !self.can_skip_current() {
+            self.is_skip_toggled = false;
+        }
+        let is_skip_requested = self.is_skip_toggled || input.is_held(Action::Skip);
+        let is_skipping = is_skip_requested && self.can_skip_current();
+
+        // 略過時動畫加速播放
+        let playback_speed = if is_skipping { SKIP_PLAYBACK_SPEED } else { 1.0 };
+        self.tween_player.update(&mut self.scene_graph, delta_time * playback_speed);
 
-                if self.background_plane.as_ref().map_or(true, |plane| plane.drawable.material.color[3] >= 1.0) &&
-                   self.center_character_plane.as_ref().map_or(true, |plane| plane.drawable.material.color[3] >= 1.0) &&
-                   self.character_name_plane.as_ref().map_or(true, |plane| plane.drawable.material.color[3] >= 1.0) &&
-                   self.content_plane.as_ref().map_or(true, |plane| plane.end_range_ratio >= 1.0) {
+        let status = self.status;
+        match status {
+            Status::EnterCurrent => {
+                if is_advance_pressed || is_skipping {
+                    self.complete_status_animation();
+                }
+                if !self.is_status_animation_playing() {
                     self.status = Status::Wait;
+                    self.wait_time = 0.0;
+                    self.wait_voice_seconds = self.voice_remaining_time;
                 }
             },
             Status::Wait => {
-                if hit_position.is_some() {
-                    let avg_item = &self.avg_items[self.current_index as usize];
-                    self.status = if avg_item.selection_items.is_some() { Status::Selection } else { Status::EndCurrent };
-                    game.audio_manager.play_sound_one_shot(CONFIRM_SOUND_FILE_PATH);
+                self.wait_time += delta_time;
+
+                let is_skip_ready = is_skipping && self.wait_time >= SKIP_WAIT_SECONDS;
+                let is_auto_ready = self.is_auto_mode && self.wait_time >= self.get_auto_delay();
+                if is_advance_pressed || is_skip_ready || is_auto_ready {
+                    if is_advance_pressed {
+                        game.audio_manager.play_sound_one_shot(CONFIRM_SOUND_FILE_PATH);
+                    }
+                    if !is_auto_ready {
+                        game.audio_manager.stop_voice();
+                        self.voice_remaining_time = 0.0;
+                    }
+                    if self.avg_items[self.current_index as usize].selection_items.is_some() {
+                        self.status = Status::Selection;
+                    } else {
+                        self.end_current(None);
+                    }
                 }
             },
             Status::Selection => {
-                if let Some(selection_background_plane) = &mut self.selection_background_plane {
-                    selection_background_plane.set_alpha(0.75);
-                }
+                // 選項一定要等玩家決定，自動略過到此為止
+                self.is_skip_toggled = false;
+                self.focused_selection_index = None;
+
+                self.tween_player.play(Animation::tween(
+                    self.selection_background_node,
+                    TweenProperty::Color((0.0, 0.0, 0.0, SELECTION_BACKGROUND_ALPHA)),
+                    SELECTION_FADE_SECONDS,
+                    Easing::QuadOut));
 
                 if let Some(selection_items) = &self.avg_items[self.current_index as usize].selection_items {
                     let selection_items_len = selection_items.len();
-                    let selection_height = 1080.0 / selection_items_len as f32;
-                    let first_bottom = selection_height / 2.0 - 30.0;
-                    self.selections_texts = Some(selection_items
-                        .iter().enumerate().map(|(index, selection_item)| {
-                            game.drawable_generator.generate_text(
-                                (480.0, first_bottom + selection_height * (selection_items_len - index - 1) as f32),
-                                -0.5,
+                    let selection_height = layout.height / selection_items_len as f32;
+                    let first_bottom = selection_height / 2.0 - 30.0 * layout.scale;
+                    for (index, selection_item) in selection_items.iter().enumerate() {
+                        let selection_text = game.drawable_generator.generate_text(
+                                (layout.width / 4.0, first_bottom + selection_height * (selection_items_len - index - 1) as f32),
+                                -0.1,
                                 &selection_item.content,
                                 1.0,
                                 (1.0, 1.0, 1.0, 1.0),
                                 FONT_PATH,
-                                60,
-                                VERTEX_SHADER,
-                                FRAGMENT_SHADER)
-                    }).collect::<Vec<_>>());
+                                layout.scale_font_size(60),
+                                ShaderSource::Name(DEFAULT_SHADER_NAME)).map_err(|error| eprintln!("{}", error)).ok();
+                        self.scene_graph.add_node(self.selection_texts_node, selection_text.map(|text| Box::new(text) as _));
+                    }
+                }
+
+                // 手把沒有游標，直接聚焦第一個選項
+                if input.last_device == InputDevice::Controller {
+                    self.focused_selection_index = Some(0);
+                    let selection_texts = self.scene_graph.get_child_components_mut::<Text>(self.selection_texts_node);
+                    highlight_texts(selection_texts, self.focused_selection_index);
                 }
                 self.status = Status::SelectionWait;
             },
             Status::SelectionWait => {
-                if let Some(hit_position) = hit_position {
-                    if let Some(selection_texts) = &mut self.selections_texts {
-                        let selection_texts_len = selection_texts.len();
-                        let avg_item = &self.avg_items[self.current_index as usize];
-
-                        for index in 0..selection_texts_len {
-                            let mut selection_text = &mut selection_texts[index];
-                            if selection_text.contains((hit_position.0 as f32, hit_position.1 as f32)) {
-                                selection_text.set_color((1.0, 1.0, 0.0, 1.0));
-                                self.next_index = avg_item.selection_items.as_ref().unwrap()[index].next_index as usize;
-                                self.status = Status::EndCurrent;
-                                game.audio_manager.play_sound_one_shot(CONFIRM_SOUND_FILE_PATH);
-                            }
-                        }
+                let mut selection_texts = self.scene_graph.get_child_components_mut::<Text>(self.selection_texts_node);
+                if !selection_texts.is_empty() {
+                    let selection_texts_len = selection_texts.len();
+                    let avg_item = &self.avg_items[self.current_index as usize];
+                    let previous_focused_selection_index = self.focused_selection_index;
+
+                    if input.is_pressed(Action::Up) {
+                        self.focused_selection_index = Some(self.focused_selection_index
+                            .map_or(selection_texts_len - 1, |index| (index + selection_texts_len - 1) % selection_texts_len));
                     }
-                }
-            },
-            Status::EndCurrent => {
-                let fade_speed_per_second = if hit_position.is_some() { IMMEDIATELY_FADE_SPEED } else { FADE_SPEED_PER_SECOND };
-                let avg_item = &self.avg_items[self.current_index as usize];
-                let next_avg_item = self.avg_items.get(self.next_index);
-
-                let is_change_background = next_avg_item
-                    .and_then(|avg_item| avg_item.background_image_path.as_ref())
-                    .is_some();
-                if is_change_background {
-                    if let Some(background_plane) = &mut self.background_plane {
-                        background_plane.set_alpha(
-                            (background_plane.drawable.material.color[3] - delta_time * fade_speed_per_second).max(0.0));
+                    if input.is_pressed(Action::Down) {
+                        self.focused_selection_index = Some(self.focused_selection_index
+                            .map_or(0, |index| (index + 1) % selection_texts_len));
                     }
-                }
-
-                let is_change_left_character = next_avg_item
-                    .and_then(|avg_item| avg_item.center_character_image_path.as_ref())
-                    .is_some();
-                if is_change_left_character {
-                    if let Some(center_character_plane) = &mut self.center_character_plane {
-                        center_character_plane.set_alpha(
-                            (center_character_plane.drawable.material.color[3] - delta_time * fade_speed_per_second).max(0.0));
+                    if let Some(hovered_index) = get_hovered_index(selection_texts.iter().map(|selection_text| &**selection_text), input) {
+                        self.focused_selection_index = Some(hovered_index);
+                        game.request_cursor(SystemCursor::Hand);
                     }
-                }
-
-                let current_character_name = avg_item
-                    .character_name
-                    .as_ref()
-                    .unwrap_or(&empty_string)
-                    .as_str();
-                let next_character_name = next_avg_item
-                    .and_then(|avg_item| avg_item.character_name.as_ref())
-                    .unwrap_or(&empty_string)
-                    .as_str();
-                let is_change_character_name = current_character_name != next_character_name;
-                if is_change_character_name {
-                    if let Some(character_name_plane) = &mut self.character_name_plane {
-                        character_name_plane.set_alpha(
-                            (character_name_plane.drawable.material.color[3] - delta_time * fade_speed_per_second).max(0.0));
+                    if self.focused_selection_index.is_some() && self.focused_selection_index != previous_focused_selection_index {
+                        game.audio_manager.play_sound_one_shot(HOVER_SOUND_FILE_PATH);
                     }
-                }
 
-                if let Some(content_plane) = &mut self.content_plane {
-                    content_plane.set_alpha(
-                        (content_plane.drawable.material.color[3] - delta_time * fade_speed_per_second).max(0.0));
-                }
+                    let mut selected_index = None;
+                    if input.is_pressed(Action::Confirm) {
+                        match self.focused_selection_index {
+                            Some(_) => selected_index = self.focused_selection_index,
+                            None => self.focused_selection_index = Some(0),
+                        }
+                    }
+                    if let Some(hit_position) = hit_position {
+                        // 手指比滑鼠不精準，觸控時放大判定範圍
+                        let padding = if input.last_device == InputDevice::Touch { TOUCH_TARGET_PADDING } else { 0.0 };
+                        selected_index = selection_texts
+                            .iter()
+                            .position(|selection_text| selection_text.contains_with_padding((hit_position.0 as f32, hit_position.1 as f32), padding));
+                    }
+                    if selected_index.is_some() {
+                        self.focused_selection_index = selected_index;
+                    }
 
-                if let Some(selection_background_plane) = &mut self.selection_background_plane {
-                    selection_background_plane.set_alpha(
-                        (selection_background_plane.drawable.material.color[3] - delta_time * fade_speed_per_second).max(0.0));
-                }
+                    if self.focused_selection_index != previous_focused_selection_index {
+                        highlight_texts(selection_texts.iter_mut().map(|selection_text| &mut **selection_text), self.focused_selection_index);
+                    }
 
-                if let Some(selection_texts) = &mut self.selections_texts {
-                    let selection_texts_len = selection_texts.len();
-                    for index in 0..selection_texts_len {
-                        let mut selection_text = &mut selection_texts[index];
-                        selection_text.set_alpha((selection_text.drawable.material.color[3] - delta_time * fade_speed_per_second).max(0.0));
+                    if let Some(selected_index) = selected_index {
+                        self.next_index = avg_item.selection_items.as_ref().unwrap()[selected_index].next_index as usize;
+                        game.audio_manager.play_sound_one_shot(CONFIRM_SOUND_FILE_PATH);
+                        self.end_current(Some(selected_index));
                     }
                 }
-
-                if self.background_plane.as_ref().map_or(true, |plane| plane.drawable.material.color[3] <= 0.0 || !is_change_background) &&
-                    self.center_character_plane.as_ref().map_or(true, |plane| plane.drawable.material.color[3] <= 0.0 || !is_change_left_character) &&
-                    self.character_name_plane.as_ref().map_or(true, |plane| plane.drawable.material.color[3] <= 0.0 || !is_change_character_name) &&
-                    self.content_plane.as_ref().map_or(true, |plane| plane.drawable.material.color[3] <= 0.0) {
+            },
+            Status::EndCurrent => {
+                if is_advance_pressed {
+                    self.complete_status_animation();
+                }
+                if !self.is_status_animation_playing() {
                     self.status = Status::LoadNext;
                 }
             },
@@ -286,38 +581,64 @@ impl Scene for GamePlayScene {
                     if self.current_index >= 0 { &self.avg_items.get(self.current_index as usize) }
                     else { &None };
                 let avg_item = &self.avg_items[self.next_index];
-
-                if let Some(background_music) = &avg_item.background_music {
+                let is_first_load = self.current_index < 0;
+                let (background_music, background_image_path, center_character_image_path) = if is_first_load {
+                    (
+                        self.find_latest_in_history(|avg_item| avg_item.background_music.as_ref()),
+                        self.find_latest_in_history(|avg_item| avg_item.background_image_path.as_ref()),
+                        self.find_latest_in_history(|avg_item| avg_item.center_character_image_path.as_ref()),
+                    )
+                } else {
+                    (
+                        avg_item.background_music.clone(),
+                        avg_item.background_image_path.clone(),
+                        avg_item.center_character_image_path.clone(),
+                    )
+                };
+
+                if let Some(background_music) = &background_music {
                     game.audio_manager.load_music(background_music);
                     game.audio_manager.play_music();
                 }
 
-                if let Some(background_image_path) = &avg_item.background_image_path {
-                    self.background_plane = match background_image_path.as_str() {
+                if let Some(voice_path) = &avg_item.voice_path {
+                    self.voice_remaining_time = game.audio_manager.play_voice(voice_path);
+                }
+
+                if let Some(background_image_path) = &background_image_path {
+                    let background_plane = match background_image_path.as_str() {
                         NO_IMAGE => None,
-                        _ => Some(game.drawable_generator.generate_plane_from_image(
-                            (0.0, 0.0, 1920.0, 1080.0),
+                        _ => game.drawable_generator.generate_plane_from_image(
+                            layout.get_cover_rect(),
                             0.0,
                             (1.0, 1.0, 1.0, 0.0),
                             Some(background_image_path),
-                            VERTEX_SHADER,
-                            FRAGMENT_SHADER
-                        ))
+                            ShaderSource::Name(DEFAULT_SHADER_NAME)
+                        ).map_err(|error| eprintln!("{}", error)).ok()
                     };
+                    self.scene_graph.set_component(self.background_node, background_plane.map(|plane| Box::new(plane) as _));
                 }
 
-                if let Some(center_character_image_path) = &avg_item.center_character_image_path {
-                    self.center_character_plane = match center_character_image_path.as_str() {
+                let mut animations = vec![fade_in(self.background_node)];
+
+                if let Some(center_character_image_path) = &center_character_image_path {
+                    let center_character_plane = match center_character_image_path.as_str() {
                         NO_IMAGE => None,
-                        _ => Some(game.drawable_generator.generate_plane_from_image(
-                            (0.0, 0.0, 1920.0, 1080.0),
-                            -0.1,
+                        _ => game.drawable_generator.generate_plane_from_image(
+                            layout.get_cover_rect(),
+                            0.0,
                             (1.0, 1.0, 1.0, 0.0),
                             Some(center_character_image_path),
-                            VERTEX_SHADER,
-                            FRAGMENT_SHADER
-                        ))
+                            ShaderSource::Name(DEFAULT_SHADER_NAME)
+                        ).map_err(|error| eprintln!("{}", error)).ok()
                     };
+                    self.scene_graph.set_component(self.center_character_node, center_character_plane.map(|plane| Box::new(plane) as _));
+                    // 淡入的同時由下往上滑到定位
+                    if let Some(center_character_plane) = self.scene_graph.get_component_mut::<Plane>(self.center_character_node) {
+                        center_character_plane.transform.position.1 = -CHARACTER_ENTER_OFFSET * layout.scale;
+                    }
+                    animations.push(fade_in(self.center_character_node));
+                    animations.push(Animation::tween(self.center_character_node, TweenProperty::Position((0.0, 0.0)), FADE_SECONDS, Easing::CubicOut));
                 }
 
                 let previous_character_name = previous_avg_item
@@ -330,39 +651,53 @@ impl Scene for GamePlayScene {
                     .unwrap_or(&empty_string)
                     .as_str();
                 let is_change_character_name = previous_character_name != current_character_name;
-                self.character_name_plane = avg_item.character_name
+                let character_name_text = avg_item.character_name
                     .as_ref()
-                    .map(|character_name| {
+                    .and_then(|character_name| {
                         game.drawable_generator.generate_text(
-                            (16.0, 385.0),
-                            -0.3,
+                            layout.scale_position((16.0, 385.0)),
+                            -0.1,
                             character_name,
                             1.0,
                             (0.5, 0.7, 1.0, if is_change_character_name { 0.0 } else { 1.0 }),
                             FONT_PATH,
-                            120,
-                            VERTEX_SHADER,
-                            FRAGMENT_SHADER
-                        )
+                            layout.scale_font_size(120),
+                            ShaderSource::Name(DEFAULT_SHADER_NAME)
+                        ).map_err(|error| eprintln!("{}", error)).ok()
                     });
+                self.scene_graph.set_component(self.character_name_node, character_name_text.map(|text| Box::new(text) as _));
+                animations.push(fade_in(self.character_name_node));
 
-                self.content_plane = avg_item.content
+                let content_text = avg_item.content
                     .as_ref()
-                    .map(|content| {
+                    .and_then(|content| {
                         game.drawable_generator.generate_text(
-                            (16.0, 260.0),
-                            -0.3,
+                            layout.scale_position((16.0, 260.0)),
+                            -0.1,
                             content,
                             0.0,
-                            (1.0, 1.0, 1.0, 0.0),
+                            (1.0, 1.0, 1.0, 1.0),
                             FONT_PATH,
-                            60,
-                            VERTEX_SHADER,
-                            FRAGMENT_SHADER
-                        )
+                            layout.scale_font_size(60),
+                            ShaderSource::Name(DEFAULT_SHADER_NAME)
+                        ).map_err(|error| eprintln!("{}", error)).ok()
                     });
 
-                self.selections_texts = None;
+                // 換背景時等背景淡入後才開始逐字顯示
+                let content_length = content_text.as_ref().map_or(0, |content_text| content_text.get_content_char_indices_count());
+                let reveal_seconds = content_length as f32 / self.settings.borrow().text_speed_per_second.max(TEXT_SPEED_RANGE.0);
+                self.scene_graph.set_component(self.content_node, content_text.map(|text| Box::new(text) as _));
+                animations.push(Animation::sequence(vec![
+                    Animation::delay(if background_image_path.is_some() { FADE_SECONDS } else { 0.0 }),
+                    Animation::tween(self.content_node, TweenProperty::TextReveal(1.0), reveal_seconds, Easing::Linear),
+                ]));
+                self.status_animation_id = Some(self.tween_player.play(Animation::parallel(animations)));
+
+                self.scene_graph.clear_children(self.selection_texts_node);
+
+                if avg_item.content.is_some() {
+                    self.backlog_indices.push(self.next_index);
+                }
 
                 self.status = Status::EnterCurrent;
                 self.current_index = self.next_index as isize;
@@ -370,52 +705,51 @@ impl Scene for GamePlayScene {
             }
         }
 
+        self.update_visibility();
     }
 
-    fn draw(&self, game: &mut Game) {
-        if let Some(background_plane) = &self.background_plane {
-            background_plane.draw(game.current_projection_matrix);
-        }
-
-        if let Some(left_character_plane) = &self.center_character_plane {
-            left_character_plane.draw(game.current_projection_matrix);
-        }
-
-        if self.content_plane.is_some() {
-            if self.character_name_plane.is_some() {
-                if let Some(frame_plane) = &self.frame_plane {
-                    frame_plane.draw(game.current_projection_matrix);
-                }
-            }
-            else {
-                if let Some(no_name_frame_plane) = &self.no_name_frame_plane {
-                    no_name_frame_plane.draw(game.current_projection_matrix);
-                }
-            }
-        }
-
-        if let Some(character_name_plane) = &self.character_name_plane {
-            character_name_plane.draw(game.current_projection_matrix);
-        }
-
-        if let Some(content_plane) = &self.content_plane {
-            content_plane.draw(game.current_projection_matrix);
-        }
+    fn get_scene_graph(&self) -> Option<&SceneGraph> {
+        Some(&self.scene_graph)
+    }
 
-        if self.status == Status::SelectionWait || self.status == Status::EndCurrent {
-            if let Some(selection_background_plane) = &self.selection_background_plane {
-                selection_background_plane.draw(game.current_projection_matrix);
-            }
+    fn get_scene_graph_mut(&mut self) -> Option<&mut SceneGraph> {
+        Some(&mut self.scene_graph)
+    }
+}
 
-            if let Some(selection_texts) = &self.selections_texts {
-                for selection_text in selection_texts {
-                    selection_text.draw(game.current_projection_matrix);
-                }
-            }
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+    use super::*;
+    use crate::engine::clock::{Clock, ManualClock};
+    use crate::engine::rendering::shader_registry::ShaderProgram;
+
+    #[test]
+    fn fade_in_steps_with_fixed_frame_time() {
+        // 測試沒有 GL context，保留一份參考讓 program 不會被釋放
+        let shader_program = ManuallyDrop::new(Rc::new(ShaderProgram::new(0)));
+        let mut scene_graph = SceneGraph::new();
+        let plane = Plane::new((0.0, 0.0, 1.0, 1.0), 0.0, (1.0, 1.0, 1.0, 0.0), None, Rc::clone(&shader_program));
+        let node_id = scene_graph.add_node(scene_graph.get_root(), Some(Box::new(plane)));
+        let mut tween_player = TweenPlayer::new();
+        let animation_id = tween_player.play(fade_in(node_id));
+
+        let clock = ManualClock::new(0.1);
+        let mut last_updated_time = clock.now();
+        let mut alphas = vec![];
+        while tween_player.is_playing(animation_id) {
+            clock.advance_frame();
+            let current_time = clock.now();
+            let delta_time = (current_time - last_updated_time) as f32;
+            last_updated_time = current_time;
+            tween_player.update(&mut scene_graph, delta_time);
+            alphas.push(scene_graph.get_component::<Plane>(node_id).map_or(0.0, Plane::get_alpha));
         }
 
-        if let Some(delta_time_text) = &self.debug_information_plane {
-            delta_time_text.draw(game.current_projection_matrix);
+        let expected_alphas = [0.2, 0.4, 0.6, 0.8, 1.0];
+        assert_eq!(alphas.len(), expected_alphas.len());
+        for (alpha, expected_alpha) in alphas.iter().zip(expected_alphas) {
+            assert!((alpha - expected_alpha).abs() < 1e-5, "{} != {}", alpha, expected_alpha);
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,47 @@
+use crate::engine::design_resolution::DesignResolution;
+
+// 美術素材都以 1920x1080 製作，其他設計解析度以寬度等比縮放介面
+const REFERENCE_WIDTH: f32 = 1920.0;
+const REFERENCE_HEIGHT: f32 = 1080.0;
+
+pub struct Layout {
+    pub width: f32,
+    pub height: f32,
+    pub scale: f32,
+}
+
+impl Layout {
+    pub fn new(design_resolution: DesignResolution) -> Self {
+        let (width, height) = design_resolution.get_size();
+        Self {
+            width,
+            height,
+            scale: width / REFERENCE_WIDTH,
+        }
+    }
+
+    pub fn get_full_screen_rect(&self) -> (f32, f32, f32, f32) {
+        (0.0, 0.0, self.width, self.height)
+    }
+
+    // 等比放大到蓋滿畫面，超出的部分裁掉
+    pub fn get_cover_rect(&self) -> (f32, f32, f32, f32) {
+        let cover_scale = (self.width / REFERENCE_WIDTH).max(self.height / REFERENCE_HEIGHT);
+        let cover_width = REFERENCE_WIDTH * cover_scale;
+        let cover_height = REFERENCE_HEIGHT * cover_scale;
+        ((self.width - cover_width) / 2.0, (self.height - cover_height) / 2.0, cover_width, cover_height)
+    }
+
+    // 對話框貼齊畫面底部
+    pub fn get_bottom_rect(&self) -> (f32, f32, f32, f32) {
+        (0.0, 0.0, self.width, REFERENCE_HEIGHT * self.scale)
+    }
+
+    pub fn scale_position(&self, position: (f32, f32)) -> (f32, f32) {
+        (position.0 * self.scale, position.1 * self.scale)
+    }
+
+    pub fn scale_font_size(&self, font_size: u16) -> u16 {
+        (font_size as f32 * self.scale).round() as u16
+    }
+}
@@ -0,0 +1,111 @@
+use sdl2::mouse::SystemCursor;
+use crate::engine::drawable_implements::generator::ShaderSource;
+use crate::engine::drawable_implements::text::Text;
+use crate::engine::game::{Game, DEFAULT_SHADER_NAME};
+use crate::engine::input::action::Action;
+use crate::engine::input::input_state::InputState;
+use crate::engine::scene_graph::{NodeId, SceneGraph};
+use crate::r00_avg_game::common::{get_hovered_index, highlight_texts, FONT_PATH, HIGHLIGHT_COLOR, HOVER_SOUND_FILE_PATH, NORMAL_COLOR};
+
+// 直向排列的選單，支援鍵盤、手把、滑鼠懸停與點擊；每個項目的文字是 node 底下的子節點
+pub struct MenuList {
+    node: NodeId,
+    items: Vec<String>,
+    left_bottom: (f32, f32),
+    line_spacing: f32,
+    font_size: u16,
+    z_index: f32,
+    focused_index: usize,
+    is_texts_generated: bool,
+}
+
+impl MenuList {
+    pub(crate) fn new(
+        node: NodeId,
+        items: Vec<String>,
+        left_bottom: (f32, f32),
+        line_spacing: f32,
+        font_size: u16,
+        z_index: f32) -> Self {
+        Self {
+            node,
+            items,
+            left_bottom,
+            line_spacing,
+            font_size,
+            z_index,
+            focused_index: 0,
+            is_texts_generated: false,
+        }
+    }
+
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.focused_index = self.focused_index.min(items.len().saturating_sub(1));
+        self.items = items;
+        self.is_texts_generated = false;
+    }
+
+    pub fn get_focused_index(&self) -> usize {
+        self.focused_index
+    }
+
+    // 回傳這一幀被選擇的項目
+    pub fn update(&mut self, game: &mut Game, scene_graph: &mut SceneGraph, input: &InputState) -> Option<usize> {
+        if !self.is_texts_generated {
+            scene_graph.clear_children(self.node);
+            let texts = self.items
+                .iter().enumerate().map(|(index, item)| {
+                    game.drawable_generator.generate_text(
+                        (self.left_bottom.0, self.left_bottom.1 - self.line_spacing * index as f32),
+                        self.z_index,
+                        item,
+                        1.0,
+                        if index == self.focused_index { HIGHLIGHT_COLOR } else { NORMAL_COLOR },
+                        FONT_PATH,
+                        self.font_size,
+                        ShaderSource::Name(DEFAULT_SHADER_NAME)).map_err(|error| eprintln!("{}", error)).ok()
+                }).collect::<Option<Vec<_>>>()?;
+            for text in texts {
+                scene_graph.add_node(self.node, Some(Box::new(text)));
+            }
+            self.is_texts_generated = true;
+        }
+
+        let texts = scene_graph.get_child_components_mut::<Text>(self.node);
+        let texts_len = texts.len();
+        if texts_len == 0 {
+            return None;
+        }
+
+        let previous_focused_index = self.focused_index;
+        if input.is_pressed(Action::Up) {
+            self.focused_index = (self.focused_index + texts_len - 1) % texts_len;
+        }
+        if input.is_pressed(Action::Down) {
+            self.focused_index = (self.focused_index + 1) % texts_len;
+        }
+        if let Some(hovered_index) = get_hovered_index(texts.iter().map(|text| &**text), input) {
+            self.focused_index = hovered_index;
+            game.request_cursor(SystemCursor::Hand);
+        }
+        if self.focused_index != previous_focused_index {
+            game.audio_manager.play_sound_one_shot(HOVER_SOUND_FILE_PATH);
+        }
+
+        let mut selected_index = if input.is_pressed(Action::Confirm) { Some(self.focused_index) } else { None };
+        if let Some(hit_position) = input.hit_position.filter(|_| input.is_pressed(Action::Advance)) {
+            selected_index = texts
+                .iter()
+                .position(|text| text.contains((hit_position.0 as f32, hit_position.1 as f32)));
+        }
+        if let Some(selected_index) = selected_index {
+            self.focused_index = selected_index;
+        }
+
+        if self.focused_index != previous_focused_index {
+            highlight_texts(texts, Some(self.focused_index));
+        }
+
+        selected_index
+    }
+}
@@ -1,2 +1,11 @@
 pub(crate) mod gameplay;
-pub(crate) mod data;
\ No newline at end of file
+pub(crate) mod data;
+pub(crate) mod layout;
+pub(crate) mod common;
+pub(crate) mod pause_menu;
+pub(crate) mod backlog;
+pub(crate) mod menu_list;
+pub(crate) mod save_slot;
+pub(crate) mod title;
+pub(crate) mod settings_menu;
+pub(crate) mod cutscene;
\ No newline at end of file
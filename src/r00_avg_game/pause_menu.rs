@@ -0,0 +1,123 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::engine::drawable_implements::generator::ShaderSource;
+use crate::engine::game::{Game, DEFAULT_SHADER_NAME};
+use crate::engine::input::action::Action;
+use crate::engine::input::input_state::InputState;
+use crate::engine::scene::Scene;
+use crate::engine::scene_graph::{NodeId, SceneGraph};
+use crate::engine::transition::Transition;
+use crate::r00_avg_game::common::{CONFIRM_SOUND_FILE_PATH, OVERLAY_BACKGROUND_COLOR};
+use crate::r00_avg_game::data::localization::{Language, TextId};
+use crate::r00_avg_game::data::save_data::SaveData;
+use crate::r00_avg_game::data::story_context::StoryContext;
+use crate::r00_avg_game::layout::Layout;
+use crate::r00_avg_game::menu_list::MenuList;
+use crate::r00_avg_game::save_slot::{SaveSlotMode, SaveSlotScene};
+use crate::r00_avg_game::settings_menu::SettingsScene;
+
+const MENU_ITEMS: [TextId; 4] = [TextId::Resume, TextId::Save, TextId::Settings, TextId::Quit];
+const MENU_RESUME_INDEX: usize = 0;
+const MENU_SAVE_INDEX: usize = 1;
+const MENU_SETTINGS_INDEX: usize = 2;
+const MENU_QUIT_INDEX: usize = 3;
+
+pub struct PauseMenuScene {
+    story_context: StoryContext,
+    save_data: Option<SaveData>,
+    menu_language: Option<Language>,
+    scene_graph: SceneGraph,
+    background_node: NodeId,
+    menu_node: NodeId,
+    menu_list: Option<MenuList>,
+}
+
+impl PauseMenuScene {
+    pub(crate) fn new(story_context: StoryContext, save_data: Option<SaveData>) -> Self {
+        let mut scene_graph = SceneGraph::new();
+        let root = scene_graph.get_root();
+        let background_node = scene_graph.add_node(root, None);
+        let menu_node = scene_graph.add_node(root, None);
+        Self {
+            story_context,
+            save_data,
+            menu_language: None,
+            scene_graph,
+            background_node,
+            menu_node,
+            menu_list: None,
+        }
+    }
+}
+
+impl Scene for PauseMenuScene {
+    fn update(&mut self, game: &mut Game, _delta_time: f32, input: &InputState) {
+        let layout = Layout::new(game.get_design_resolution());
+
+        if !self.scene_graph.has_component(self.background_node) {
+            let background_plane = game.drawable_generator.generate_plane_from_image(
+                layout.get_full_screen_rect(),
+                -0.6,
+                OVERLAY_BACKGROUND_COLOR,
+                None,
+                ShaderSource::Name(DEFAULT_SHADER_NAME)
+            ).map_err(|error| eprintln!("{}", error)).ok();
+            self.scene_graph.set_component(self.background_node, background_plane.map(|plane| Box::new(plane) as _));
+        }
+
+        let language = self.story_context.settings.borrow().language;
+        if self.menu_language != Some(language) {
+            self.menu_list = None;
+            self.menu_language = Some(language);
+        }
+
+        if self.menu_list.is_none() {
+            self.menu_list = Some(MenuList::new(
+                self.menu_node,
+                MENU_ITEMS.iter().map(|text_id| language.get_text(*text_id).to_string()).collect(),
+                (layout.width / 2.0 - 200.0 * layout.scale, layout.height / 2.0 + 270.0 * layout.scale),
+                180.0 * layout.scale,
+                layout.scale_font_size(72),
+                -0.7));
+        }
+
+        if input.is_pressed(Action::Menu) || input.is_pressed(Action::Back) {
+            game.pop_scene(Transition::None);
+            return;
+        }
+
+        let selected_index = self.menu_list.as_mut().and_then(|menu_list| menu_list.update(game, &mut self.scene_graph, input));
+        match selected_index {
+            Some(MENU_RESUME_INDEX) => {
+                game.pop_scene(Transition::None);
+                game.audio_manager.play_sound_one_shot(CONFIRM_SOUND_FILE_PATH);
+            },
+            Some(MENU_SAVE_INDEX) => {
+                if let Some(save_data) = &self.save_data {
+                    let save_slot_scene = SaveSlotScene::new(self.story_context.clone(), SaveSlotMode::Save(save_data.clone()));
+                    game.push_scene(Rc::new(RefCell::new(save_slot_scene)), Transition::None);
+                    game.audio_manager.play_sound_one_shot(CONFIRM_SOUND_FILE_PATH);
+                }
+            },
+            Some(MENU_SETTINGS_INDEX) => {
+                let settings_scene = SettingsScene::new(self.story_context.clone());
+                game.push_scene(Rc::new(RefCell::new(settings_scene)), Transition::None);
+                game.audio_manager.play_sound_one_shot(CONFIRM_SOUND_FILE_PATH);
+            },
+            Some(MENU_QUIT_INDEX) => game.quit(),
+            _ => {}
+        }
+    }
+
+    fn get_scene_graph(&self) -> Option<&SceneGraph> {
+        Some(&self.scene_graph)
+    }
+
+    fn get_scene_graph_mut(&mut self) -> Option<&mut SceneGraph> {
+        Some(&mut self.scene_graph)
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
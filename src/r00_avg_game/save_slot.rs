@@ -0,0 +1,158 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::engine::drawable_implements::generator::ShaderSource;
+use crate::engine::game::{Game, DEFAULT_SHADER_NAME};
+use crate::engine::input::action::Action;
+use crate::engine::input::input_state::InputState;
+use crate::engine::scene::Scene;
+use crate::engine::scene_graph::{NodeId, SceneGraph};
+use crate::engine::transition::Transition;
+use crate::r00_avg_game::common::{CONFIRM_SOUND_FILE_PATH, FONT_PATH, NORMAL_COLOR, OVERLAY_BACKGROUND_COLOR, SCENE_TRANSITION};
+use crate::r00_avg_game::data::localization::TextId;
+use crate::r00_avg_game::data::save_data::{SaveData, SAVE_SLOT_COUNT};
+use crate::r00_avg_game::data::story_context::StoryContext;
+use crate::r00_avg_game::gameplay::GamePlayScene;
+use crate::r00_avg_game::layout::Layout;
+use crate::r00_avg_game::menu_list::MenuList;
+
+const SLOT_PREVIEW_LENGTH: usize = 16;
+
+pub enum SaveSlotMode {
+    Save(SaveData),
+    Load,
+}
+
+// 存檔與讀檔共用的存檔欄位畫面
+pub struct SaveSlotScene {
+    story_context: StoryContext,
+    mode: SaveSlotMode,
+    scene_graph: SceneGraph,
+    background_node: NodeId,
+    title_node: NodeId,
+    slot_list_node: NodeId,
+    slot_list: Option<MenuList>,
+}
+
+impl SaveSlotScene {
+    pub(crate) fn new(story_context: StoryContext, mode: SaveSlotMode) -> Self {
+        let mut scene_graph = SceneGraph::new();
+        let root = scene_graph.get_root();
+        let background_node = scene_graph.add_node(root, None);
+        let title_node = scene_graph.add_node(root, None);
+        let slot_list_node = scene_graph.add_node(root, None);
+        Self {
+            story_context,
+            mode,
+            scene_graph,
+            background_node,
+            title_node,
+            slot_list_node,
+            slot_list: None,
+        }
+    }
+
+    fn get_slot_items(&self) -> Vec<String> {
+        let language = self.story_context.settings.borrow().language;
+        (0..SAVE_SLOT_COUNT)
+            .map(|slot| {
+                let preview = SaveData::load(slot, self.story_context.avg_items.len())
+                    .and_then(|save_data| self.story_context.avg_items.get(save_data.current_index))
+                    .map_or_else(|| language.get_text(TextId::EmptySlot).to_string(), |avg_item| {
+                        avg_item.content
+                            .as_deref()
+                            .unwrap_or_default()
+                            .chars()
+                            .take(SLOT_PREVIEW_LENGTH)
+                            .collect()
+                    });
+                format!("{} {}    {}", language.get_text(TextId::SaveSlot), slot + 1, preview)
+            })
+            .collect()
+    }
+}
+
+impl Scene for SaveSlotScene {
+    fn update(&mut self, game: &mut Game, _delta_time: f32, input: &InputState) {
+        let layout = Layout::new(game.get_design_resolution());
+
+        if !self.scene_graph.has_component(self.background_node) {
+            let background_plane = game.drawable_generator.generate_plane_from_image(
+                layout.get_full_screen_rect(),
+                -0.6,
+                OVERLAY_BACKGROUND_COLOR,
+                None,
+                ShaderSource::Name(DEFAULT_SHADER_NAME)
+            ).map_err(|error| eprintln!("{}", error)).ok();
+            self.scene_graph.set_component(self.background_node, background_plane.map(|plane| Box::new(plane) as _));
+        }
+
+        if !self.scene_graph.has_component(self.title_node) {
+            let language = self.story_context.settings.borrow().language;
+            let title_text = game.drawable_generator.generate_text(
+                (120.0 * layout.scale, layout.height - 200.0 * layout.scale),
+                -0.7,
+                &language.get_text(match self.mode {
+                    SaveSlotMode::Save(_) => TextId::Save,
+                    SaveSlotMode::Load => TextId::Load,
+                }).to_string(),
+                1.0,
+                NORMAL_COLOR,
+                FONT_PATH,
+                layout.scale_font_size(96),
+                ShaderSource::Name(DEFAULT_SHADER_NAME)
+            ).map_err(|error| eprintln!("{}", error)).ok();
+            self.scene_graph.set_component(self.title_node, title_text.map(|text| Box::new(text) as _));
+        }
+
+        if self.slot_list.is_none() {
+            self.slot_list = Some(MenuList::new(
+                self.slot_list_node,
+                self.get_slot_items(),
+                (200.0 * layout.scale, layout.height - 420.0 * layout.scale),
+                180.0 * layout.scale,
+                layout.scale_font_size(60),
+                -0.7));
+        }
+
+        if input.is_pressed(Action::Back) || input.is_pressed(Action::Menu) {
+            game.pop_scene(Transition::None);
+            return;
+        }
+
+        let Some(selected_slot) = self.slot_list.as_mut().and_then(|slot_list| slot_list.update(game, &mut self.scene_graph, input)) else {
+            return;
+        };
+
+        match &mut self.mode {
+            SaveSlotMode::Save(save_data) => {
+                save_data.save(selected_slot);
+                game.audio_manager.play_sound_one_shot(CONFIRM_SOUND_FILE_PATH);
+                let slot_items = self.get_slot_items();
+                if let Some(slot_list) = &mut self.slot_list {
+                    slot_list.set_items(slot_items);
+                }
+            },
+            SaveSlotMode::Load => {
+                // 空的欄位無法讀取
+                if let Some(save_data) = SaveData::load(selected_slot, self.story_context.avg_items.len()) {
+                    game.audio_manager.play_sound_one_shot(CONFIRM_SOUND_FILE_PATH);
+                    let game_play_scene = GamePlayScene::new(self.story_context.clone(), Some(save_data));
+                    game.pop_scene(Transition::None);
+                    game.change_scene(Rc::new(RefCell::new(game_play_scene)), SCENE_TRANSITION);
+                }
+            },
+        }
+    }
+
+    fn get_scene_graph(&self) -> Option<&SceneGraph> {
+        Some(&self.scene_graph)
+    }
+
+    fn get_scene_graph_mut(&mut self) -> Option<&mut SceneGraph> {
+        Some(&mut self.scene_graph)
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,242 @@
+use crate::engine::drawable_implements::generator::ShaderSource;
+use crate::engine::game::{Game, DEFAULT_SHADER_NAME};
+use crate::engine::input::action::Action;
+use crate::engine::input::input_state::InputState;
+use crate::engine::scene::Scene;
+use crate::engine::scene_graph::{NodeId, SceneGraph};
+use crate::engine::transition::Transition;
+use crate::r00_avg_game::common::{CONFIRM_SOUND_FILE_PATH, FONT_PATH, NORMAL_COLOR, OVERLAY_BACKGROUND_COLOR};
+use crate::r00_avg_game::data::localization::TextId;
+use crate::r00_avg_game::data::settings::{Settings, AUTO_DELAY_PER_CHARACTER_RANGE, AUTO_DELAY_RANGE, RATIO_RANGE, SETTINGS_PATH, TEXT_SPEED_RANGE};
+use crate::r00_avg_game::data::story_context::StoryContext;
+use crate::r00_avg_game::layout::Layout;
+use crate::r00_avg_game::menu_list::MenuList;
+
+#[derive(Copy, Clone, PartialEq)]
+enum SettingItem {
+    BgmVolume,
+    SeVolume,
+    VoiceVolume,
+    TextSpeed,
+    AutoDelay,
+    AutoDelayPerCharacter,
+    WindowOpacity,
+    Fullscreen,
+    SkipUnread,
+    Language,
+    Back,
+}
+
+const SETTING_ITEMS: [SettingItem; 11] = [
+    SettingItem::BgmVolume,
+    SettingItem::SeVolume,
+    SettingItem::VoiceVolume,
+    SettingItem::TextSpeed,
+    SettingItem::AutoDelay,
+    SettingItem::AutoDelayPerCharacter,
+    SettingItem::WindowOpacity,
+    SettingItem::Fullscreen,
+    SettingItem::SkipUnread,
+    SettingItem::Language,
+    SettingItem::Back,
+];
+const RATIO_STEP: f32 = 0.1;
+const TEXT_SPEED_STEP: f32 = 5.0;
+const AUTO_DELAY_STEP: f32 = 0.5;
+const AUTO_DELAY_PER_CHARACTER_STEP: f32 = 0.02;
+
+// 套用需要通知引擎的設定，啟動時與每次修改後呼叫
+pub fn apply_settings(game: &mut Game, settings: &Settings) {
+    game.audio_manager.set_music_volume(settings.bgm_volume);
+    game.audio_manager.set_sound_volume(settings.se_volume);
+    game.audio_manager.set_voice_volume(settings.voice_volume);
+    game.set_fullscreen(settings.is_fullscreen);
+}
+
+pub struct SettingsScene {
+    story_context: StoryContext,
+    scene_graph: SceneGraph,
+    background_node: NodeId,
+    title_node: NodeId,
+    setting_list_node: NodeId,
+    setting_list: Option<MenuList>,
+}
+
+impl SettingsScene {
+    pub(crate) fn new(story_context: StoryContext) -> Self {
+        let mut scene_graph = SceneGraph::new();
+        let root = scene_graph.get_root();
+        let background_node = scene_graph.add_node(root, None);
+        let title_node = scene_graph.add_node(root, None);
+        let setting_list_node = scene_graph.add_node(root, None);
+        Self {
+            story_context,
+            scene_graph,
+            background_node,
+            title_node,
+            setting_list_node,
+            setting_list: None,
+        }
+    }
+
+    fn get_setting_items(&self) -> Vec<String> {
+        let settings = self.story_context.settings.borrow();
+        let language = settings.language;
+        let get_switch_text = |is_on: bool| language.get_text(if is_on { TextId::On } else { TextId::Off });
+        SETTING_ITEMS
+            .iter()
+            .map(|setting_item| {
+                let (text_id, value) = match setting_item {
+                    SettingItem::BgmVolume => (TextId::BgmVolume, format_ratio(settings.bgm_volume)),
+                    SettingItem::SeVolume => (TextId::SeVolume, format_ratio(settings.se_volume)),
+                    SettingItem::VoiceVolume => (TextId::VoiceVolume, format_ratio(settings.voice_volume)),
+                    SettingItem::TextSpeed => (TextId::TextSpeed, format!("{}", settings.text_speed_per_second)),
+                    SettingItem::AutoDelay => (TextId::AutoDelay, format!("{:.1}s", settings.auto_base_delay_seconds)),
+                    SettingItem::AutoDelayPerCharacter => (TextId::AutoDelayPerCharacter, format!("{:.2}s", settings.auto_delay_per_character_seconds)),
+                    SettingItem::WindowOpacity => (TextId::WindowOpacity, format_ratio(settings.text_window_opacity)),
+                    SettingItem::Fullscreen => (TextId::Fullscreen, get_switch_text(settings.is_fullscreen).to_string()),
+                    SettingItem::SkipUnread => (TextId::SkipUnread, get_switch_text(settings.is_skip_all).to_string()),
+                    SettingItem::Language => (TextId::Language, language.get_display_name().to_string()),
+                    SettingItem::Back => return language.get_text(TextId::Back).to_string(),
+                };
+                format!("{}    < {} >", language.get_text(text_id), value)
+            })
+            .collect()
+    }
+
+    // direction 為 1 或 -1，is_wrapping 時超過上限會回到下限（用於點擊與確認鍵）
+    fn adjust_setting(&self, setting_item: SettingItem, direction: i32, is_wrapping: bool) {
+        let mut settings = self.story_context.settings.borrow_mut();
+        let step = |value: f32, step: f32, range: (f32, f32)| {
+            let next_value = value + step * direction as f32;
+            if is_wrapping && next_value > range.1 + f32::EPSILON {
+                range.0
+            } else {
+                // 避免浮點數累加誤差
+                ((next_value / step).round() * step).clamp(range.0, range.1)
+            }
+        };
+        match setting_item {
+            SettingItem::BgmVolume => settings.bgm_volume = step(settings.bgm_volume, RATIO_STEP, RATIO_RANGE),
+            SettingItem::SeVolume => settings.se_volume = step(settings.se_volume, RATIO_STEP, RATIO_RANGE),
+            SettingItem::VoiceVolume => settings.voice_volume = step(settings.voice_volume, RATIO_STEP, RATIO_RANGE),
+            SettingItem::TextSpeed => settings.text_speed_per_second = step(settings.text_speed_per_second, TEXT_SPEED_STEP, TEXT_SPEED_RANGE),
+            SettingItem::AutoDelay => settings.auto_base_delay_seconds = step(settings.auto_base_delay_seconds, AUTO_DELAY_STEP, AUTO_DELAY_RANGE),
+            SettingItem::AutoDelayPerCharacter => settings.auto_delay_per_character_seconds = step(
+                settings.auto_delay_per_character_seconds,
+                AUTO_DELAY_PER_CHARACTER_STEP,
+                AUTO_DELAY_PER_CHARACTER_RANGE),
+            SettingItem::WindowOpacity => settings.text_window_opacity = step(settings.text_window_opacity, RATIO_STEP, RATIO_RANGE),
+            SettingItem::Fullscreen => settings.is_fullscreen = !settings.is_fullscreen,
+            SettingItem::SkipUnread => settings.is_skip_all = !settings.is_skip_all,
+            SettingItem::Language => settings.language = settings.language.next(),
+            SettingItem::Back => {}
+        }
+    }
+
+    fn close(&self, game: &mut Game) {
+        self.story_context.settings.borrow().save(SETTINGS_PATH);
+        game.pop_scene(Transition::None);
+    }
+}
+
+fn format_ratio(ratio: f32) -> String {
+    format!("{}%", (ratio * 100.0).round())
+}
+
+impl Scene for SettingsScene {
+    fn update(&mut self, game: &mut Game, _delta_time: f32, input: &InputState) {
+        let layout = Layout::new(game.get_design_resolution());
+
+        if !self.scene_graph.has_component(self.background_node) {
+            let background_plane = game.drawable_generator.generate_plane_from_image(
+                layout.get_full_screen_rect(),
+                -0.6,
+                OVERLAY_BACKGROUND_COLOR,
+                None,
+                ShaderSource::Name(DEFAULT_SHADER_NAME)
+            ).map_err(|error| eprintln!("{}", error)).ok();
+            self.scene_graph.set_component(self.background_node, background_plane.map(|plane| Box::new(plane) as _));
+        }
+
+        if !self.scene_graph.has_component(self.title_node) {
+            let language = self.story_context.settings.borrow().language;
+            let title_text = game.drawable_generator.generate_text(
+                (120.0 * layout.scale, layout.height - 160.0 * layout.scale),
+                -0.7,
+                &language.get_text(TextId::Settings).to_string(),
+                1.0,
+                NORMAL_COLOR,
+                FONT_PATH,
+                layout.scale_font_size(84),
+                ShaderSource::Name(DEFAULT_SHADER_NAME)
+            ).map_err(|error| eprintln!("{}", error)).ok();
+            self.scene_graph.set_component(self.title_node, title_text.map(|text| Box::new(text) as _));
+        }
+
+        if self.setting_list.is_none() {
+            self.setting_list = Some(MenuList::new(
+                self.setting_list_node,
+                self.get_setting_items(),
+                (240.0 * layout.scale, layout.height - 280.0 * layout.scale),
+                72.0 * layout.scale,
+                layout.scale_font_size(48),
+                -0.7));
+        }
+
+        if input.is_pressed(Action::Back) || input.is_pressed(Action::Menu) {
+            self.close(game);
+            return;
+        }
+
+        let Some(setting_list) = &mut self.setting_list else {
+            return;
+        };
+        let selected_index = setting_list.update(game, &mut self.scene_graph, input);
+        let focused_item = SETTING_ITEMS[setting_list.get_focused_index()];
+
+        let adjustment = if let Some(selected_index) = selected_index {
+            Some((SETTING_ITEMS[selected_index], 1, true))
+        } else if input.is_pressed(Action::Left) {
+            Some((focused_item, -1, false))
+        } else if input.is_pressed(Action::Right) {
+            Some((focused_item, 1, false))
+        } else {
+            None
+        };
+
+        match adjustment {
+            Some((SettingItem::Back, _, _)) => {
+                game.audio_manager.play_sound_one_shot(CONFIRM_SOUND_FILE_PATH);
+                self.close(game);
+            },
+            Some((setting_item, direction, is_wrapping)) => {
+                let previous_language = self.story_context.settings.borrow().language;
+                self.adjust_setting(setting_item, direction, is_wrapping);
+                apply_settings(game, &self.story_context.settings.borrow());
+                game.audio_manager.play_sound_one_shot(CONFIRM_SOUND_FILE_PATH);
+
+                if self.story_context.settings.borrow().language != previous_language {
+                    self.scene_graph.set_component(self.title_node, None);
+                }
+                let setting_items = self.get_setting_items();
+                if let Some(setting_list) = &mut self.setting_list {
+                    setting_list.set_items(setting_items);
+                }
+            },
+            None => {}
+        }
+    }
+
+    fn get_scene_graph(&self) -> Option<&SceneGraph> {
+        Some(&self.scene_graph)
+    }
+
+    fn get_scene_graph_mut(&mut self) -> Option<&mut SceneGraph> {
+        Some(&mut self.scene_graph)
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,213 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::engine::drawable_implements::generator::ShaderSource;
+use crate::engine::game::{Game, DEFAULT_SHADER_NAME};
+use crate::engine::input::input_state::InputState;
+use crate::engine::scene::Scene;
+use crate::engine::scene_graph::{NodeId, SceneGraph};
+use crate::engine::transition::Transition;
+use crate::r00_avg_game::common::{CONFIRM_SOUND_FILE_PATH, FONT_PATH, NORMAL_COLOR, SCENE_TRANSITION};
+use crate::r00_avg_game::cutscene::CutsceneScene;
+use crate::r00_avg_game::data::cutscene_script::CutsceneScript;
+use crate::r00_avg_game::data::localization::{Language, TextId};
+use crate::r00_avg_game::data::save_data::SaveData;
+use crate::r00_avg_game::data::story_context::StoryContext;
+use crate::r00_avg_game::gameplay::GamePlayScene;
+use crate::r00_avg_game::layout::Layout;
+use crate::r00_avg_game::menu_list::MenuList;
+use crate::r00_avg_game::save_slot::{SaveSlotMode, SaveSlotScene};
+use crate::r00_avg_game::settings_menu::SettingsScene;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum TitleEntry {
+    NewGame,
+    Continue,
+    Load,
+    Settings,
+    Quit,
+}
+
+impl TitleEntry {
+    fn get_text_id(&self) -> TextId {
+        match self {
+            TitleEntry::NewGame => TextId::NewGame,
+            TitleEntry::Continue => TextId::Continue,
+            TitleEntry::Load => TextId::Load,
+            TitleEntry::Settings => TextId::Settings,
+            TitleEntry::Quit => TextId::Quit,
+        }
+    }
+}
+
+pub struct TitleConfig {
+    pub background_image_path: Option<String>,
+    pub logo_image_path: Option<String>,
+    pub title: Option<String>,
+    pub background_music: Option<String>,
+    // 開新遊戲時先播放的開場動畫腳本
+    pub opening_cutscene_path: Option<String>,
+    pub entries: Vec<TitleEntry>,
+}
+
+pub struct TitleScene {
+    story_context: StoryContext,
+    config: TitleConfig,
+    visible_entries: Vec<TitleEntry>,
+    menu_language: Option<Language>,
+    is_music_started: bool,
+    scene_graph: SceneGraph,
+    background_node: NodeId,
+    logo_node: NodeId,
+    title_node: NodeId,
+    menu_node: NodeId,
+    menu_list: Option<MenuList>,
+}
+
+impl TitleScene {
+    pub(crate) fn new(story_context: StoryContext, config: TitleConfig) -> Self {
+        let mut scene_graph = SceneGraph::new();
+        let root = scene_graph.get_root();
+        let background_node = scene_graph.add_node(root, None);
+        let logo_node = scene_graph.add_node(root, None);
+        let title_node = scene_graph.add_node(root, None);
+        let menu_node = scene_graph.add_node(root, None);
+        Self {
+            story_context,
+            config,
+            visible_entries: Vec::new(),
+            menu_language: None,
+            is_music_started: false,
+            scene_graph,
+            background_node,
+            logo_node,
+            title_node,
+            menu_node,
+            menu_list: None,
+        }
+    }
+
+    fn start_game(&self, game: &mut Game, save_data: Option<SaveData>) {
+        let game_play_scene = GamePlayScene::new(self.story_context.clone(), save_data);
+        game.change_scene(Rc::new(RefCell::new(game_play_scene)), SCENE_TRANSITION);
+    }
+
+    fn start_new_game(&self, game: &mut Game) {
+        let Some(opening_cutscene_path) = &self.config.opening_cutscene_path else {
+            self.start_game(game, None);
+            return;
+        };
+        let cutscene_scene = CutsceneScene::new(self.story_context.clone(), CutsceneScript::load(opening_cutscene_path));
+        game.change_scene(Rc::new(RefCell::new(cutscene_scene)), SCENE_TRANSITION);
+    }
+}
+
+impl Scene for TitleScene {
+    fn update(&mut self, game: &mut Game, _delta_time: f32, input: &InputState) {
+        let layout = Layout::new(game.get_design_resolution());
+
+        if !self.is_music_started {
+            if let Some(background_music) = &self.config.background_music {
+                game.audio_manager.load_music(background_music);
+                game.audio_manager.play_music();
+            }
+            self.is_music_started = true;
+        }
+
+        if !self.scene_graph.has_component(self.background_node) {
+            let background_plane = self.config.background_image_path
+                .as_ref()
+                .and_then(|background_image_path| game.drawable_generator.generate_plane_from_image(
+                    layout.get_cover_rect(),
+                    0.0,
+                    (1.0, 1.0, 1.0, 1.0),
+                    Some(background_image_path),
+                    ShaderSource::Name(DEFAULT_SHADER_NAME)
+                ).map_err(|error| eprintln!("{}", error)).ok());
+            self.scene_graph.set_component(self.background_node, background_plane.map(|plane| Box::new(plane) as _));
+        }
+
+        if !self.scene_graph.has_component(self.logo_node) {
+            let logo_plane = self.config.logo_image_path
+                .as_ref()
+                .and_then(|logo_image_path| game.drawable_generator.generate_plane_from_image(
+                    (layout.width / 2.0 - 480.0 * layout.scale, layout.height - 480.0 * layout.scale, 960.0 * layout.scale, 360.0 * layout.scale),
+                    -0.1,
+                    (1.0, 1.0, 1.0, 1.0),
+                    Some(logo_image_path),
+                    ShaderSource::Name(DEFAULT_SHADER_NAME)
+                ).map_err(|error| eprintln!("{}", error)).ok());
+            self.scene_graph.set_component(self.logo_node, logo_plane.map(|plane| Box::new(plane) as _));
+        }
+
+        if !self.scene_graph.has_component(self.title_node) {
+            let title_text = self.config.title
+                .as_ref()
+                .and_then(|title| {
+                    // 依實際文字寬度置中
+                    let (title_width, _) = game.drawable_generator.measure_text(title, FONT_PATH, layout.scale_font_size(144));
+                    game.drawable_generator.generate_text(
+                        ((layout.width - title_width) / 2.0, layout.height - 360.0 * layout.scale),
+                        -0.3,
+                        title,
+                        1.0,
+                        NORMAL_COLOR,
+                        FONT_PATH,
+                        layout.scale_font_size(144),
+                        ShaderSource::Name(DEFAULT_SHADER_NAME)).map_err(|error| eprintln!("{}", error)).ok()
+                });
+            self.scene_graph.set_component(self.title_node, title_text.map(|text| Box::new(text) as _));
+        }
+
+        // 語言變更後重建選單文字
+        let language = self.story_context.settings.borrow().language;
+        if self.menu_language != Some(language) {
+            self.menu_list = None;
+            self.menu_language = Some(language);
+        }
+
+        if self.menu_list.is_none() {
+            // 沒有存檔時不顯示「繼續遊戲」
+            let has_save = SaveData::load_latest(self.story_context.avg_items.len()).is_some();
+            self.visible_entries = self.config.entries
+                .iter()
+                .copied()
+                .filter(|entry| has_save || *entry != TitleEntry::Continue)
+                .collect();
+            self.menu_list = Some(MenuList::new(
+                self.menu_node,
+                self.visible_entries.iter().map(|entry| language.get_text(entry.get_text_id()).to_string()).collect(),
+                (layout.width / 2.0 - 160.0 * layout.scale, layout.height / 2.0 - 40.0 * layout.scale),
+                110.0 * layout.scale,
+                layout.scale_font_size(60),
+                -0.3));
+        }
+
+        let selected_index = self.menu_list.as_mut().and_then(|menu_list| menu_list.update(game, &mut self.scene_graph, input));
+        let Some(selected_entry) = selected_index.and_then(|selected_index| self.visible_entries.get(selected_index)) else {
+            return;
+        };
+
+        game.audio_manager.play_sound_one_shot(CONFIRM_SOUND_FILE_PATH);
+        match selected_entry {
+            TitleEntry::NewGame => self.start_new_game(game),
+            TitleEntry::Continue => self.start_game(game, SaveData::load_latest(self.story_context.avg_items.len())),
+            TitleEntry::Load => {
+                let save_slot_scene = SaveSlotScene::new(self.story_context.clone(), SaveSlotMode::Load);
+                game.push_scene(Rc::new(RefCell::new(save_slot_scene)), Transition::None);
+            },
+            TitleEntry::Settings => {
+                let settings_scene = SettingsScene::new(self.story_context.clone());
+                game.push_scene(Rc::new(RefCell::new(settings_scene)), Transition::None);
+            },
+            TitleEntry::Quit => game.quit(),
+        }
+    }
+
+    fn get_scene_graph(&self) -> Option<&SceneGraph> {
+        Some(&self.scene_graph)
+    }
+
+    fn get_scene_graph_mut(&mut self) -> Option<&mut SceneGraph> {
+        Some(&mut self.scene_graph)
+    }
+}
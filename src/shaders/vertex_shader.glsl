@@ -4,6 +4,7 @@ precision highp float;
 layout(location = 0) in vec3 aPos;
 layout(location = 1) in vec4 aColor;
 layout(location = 2) in vec2 aTexCoord;
+layout(location = 3) in vec4 aTint;
 
 uniform mat4 uProjection;
 
@@ -13,6 +14,6 @@ out vec2 TexCoord;
 void main()
 {
     gl_Position = uProjection * vec4(aPos, 1.0);
-    ourColor = aColor;
+    ourColor = aColor * aTint;
     TexCoord = aTexCoord;
 }
\ No newline at end of file